@@ -0,0 +1,274 @@
+//! Offscreen render targets.
+//!
+//! ```ignore
+//! let target = gl
+//!     .framebuffer_builder()
+//!     .color_texture(512, 512, uni_gl::PixelFormat::Rgba)
+//!     .depth_renderbuffer()
+//!     .build()
+//!     .expect("incomplete framebuffer");
+//! target.bind();
+//! // render the scene, then sample target.color_texture()
+//! target.unbind();
+//! ```
+
+use crate::common::*;
+use crate::glenum::*;
+use crate::GLContext;
+
+/// client format and type used to allocate a texture of the given internal format
+fn texture_format(internal_format: PixelFormat) -> (PixelFormat, PixelType) {
+    match internal_format {
+        PixelFormat::R8 => (PixelFormat::Red, PixelType::UnsignedByte),
+        PixelFormat::Rg8 => (PixelFormat::Rg, PixelType::UnsignedByte),
+        PixelFormat::Rgb8 => (PixelFormat::Rgb, PixelType::UnsignedByte),
+        PixelFormat::Rgba8 | PixelFormat::Srgb8Alpha8 => {
+            (PixelFormat::Rgba, PixelType::UnsignedByte)
+        }
+        PixelFormat::R16f | PixelFormat::R32f => (PixelFormat::Red, PixelType::Float),
+        PixelFormat::Rg16f | PixelFormat::Rg32f => (PixelFormat::Rg, PixelType::Float),
+        PixelFormat::Rgba16f | PixelFormat::Rgba32f => (PixelFormat::Rgba, PixelType::Float),
+        _ => (internal_format, PixelType::UnsignedByte),
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum DepthKind {
+    Renderbuffer,
+    Texture,
+}
+
+/// the depth attachment of a [`RenderTarget`]
+#[derive(Debug)]
+pub enum DepthBuffer {
+    /// a depth renderbuffer, only usable for depth testing
+    Renderbuffer(WebGLRenderBuffer),
+    /// a depth texture that can be sampled, for example as a shadow map
+    Texture(WebGLTexture),
+}
+
+/// Builds a [`RenderTarget`], obtained with [`GLContext::framebuffer_builder`].
+pub struct FramebufferBuilder<'a> {
+    gl: &'a GLContext,
+    width: u16,
+    height: u16,
+    color: Option<PixelFormat>,
+    depth: Option<DepthKind>,
+}
+
+impl<'a> FramebufferBuilder<'a> {
+    /// start a framebuffer without attachment
+    pub fn new(gl: &'a GLContext) -> FramebufferBuilder<'a> {
+        FramebufferBuilder {
+            gl,
+            width: 0,
+            height: 0,
+            color: None,
+            depth: None,
+        }
+    }
+
+    /// set the size of the attachments. Only needed for targets without color texture
+    pub fn size(mut self, width: u16, height: u16) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// render the colors into a texture of the given internal format
+    pub fn color_texture(mut self, width: u16, height: u16, format: PixelFormat) -> Self {
+        self.color = Some(format);
+        self.size(width, height)
+    }
+
+    /// add a 16 bits depth renderbuffer
+    pub fn depth_renderbuffer(mut self) -> Self {
+        self.depth = Some(DepthKind::Renderbuffer);
+        self
+    }
+
+    /// add a depth texture. Requires the WEBGL_depth_texture extension on WebGL 1.0
+    pub fn depth_texture(mut self) -> Self {
+        self.depth = Some(DepthKind::Texture);
+        self
+    }
+
+    fn create_texture(
+        &self,
+        format: PixelFormat,
+        kind: PixelType,
+        internal_format: PixelFormat,
+    ) -> WebGLTexture {
+        let gl = self.gl;
+        let texture = gl.create_texture();
+        gl.bind_texture(&texture);
+        gl.set_min_filter(TextureKind::Texture2d, TextureMinFilter::Linear);
+        gl.set_mag_filter(TextureKind::Texture2d, TextureMagFilter::Linear);
+        gl.set_wrap_s(TextureKind::Texture2d, TextureWrap::ClampToEdge);
+        gl.set_wrap_t(TextureKind::Texture2d, TextureWrap::ClampToEdge);
+        if internal_format as u32 == format as u32 {
+            gl.tex_image2d(
+                TextureBindPoint::Texture2d,
+                0,
+                self.width,
+                self.height,
+                format,
+                kind,
+                &[],
+            );
+        } else {
+            gl.tex_image2d_ext(
+                TextureBindPoint::Texture2d,
+                0,
+                internal_format,
+                self.width,
+                self.height,
+                format,
+                kind,
+                &[],
+            );
+        }
+        gl.unbind_texture();
+        texture
+    }
+
+    /// create the framebuffer and its attachments, then check that it is complete.
+    ///
+    /// The objects are deleted if the framebuffer is incomplete.
+    pub fn build(self) -> Result<RenderTarget<'a>, FramebufferStatus> {
+        let gl = self.gl;
+        let framebuffer = gl.create_framebuffer();
+        gl.bind_framebuffer(Buffers::Framebuffer, &framebuffer);
+
+        let color = self.color.map(|internal_format| {
+            let (format, kind) = texture_format(internal_format);
+            let texture = self.create_texture(format, kind, internal_format);
+            gl.framebuffer_texture2d(
+                Buffers::Framebuffer,
+                Buffers::ColorAttachment0,
+                TextureBindPoint::Texture2d,
+                &texture,
+                0,
+            );
+            texture
+        });
+
+        let depth = self.depth.map(|kind| match kind {
+            DepthKind::Renderbuffer => {
+                let renderbuffer = gl.create_renderbuffer();
+                gl.bind_renderbuffer(&renderbuffer);
+                gl.renderbuffer_storage(PixelFormat::DepthComponent16, self.width, self.height);
+                gl.unbind_renderbuffer();
+                gl.framebuffer_renderbuffer(
+                    Buffers::Framebuffer,
+                    Buffers::DepthAttachment,
+                    &renderbuffer,
+                );
+                DepthBuffer::Renderbuffer(renderbuffer)
+            }
+            DepthKind::Texture => {
+                let texture = self.create_texture(
+                    PixelFormat::DepthComponent,
+                    PixelType::UnsignedShort,
+                    PixelFormat::DepthComponent,
+                );
+                gl.framebuffer_texture2d(
+                    Buffers::Framebuffer,
+                    Buffers::DepthAttachment,
+                    TextureBindPoint::Texture2d,
+                    &texture,
+                    0,
+                );
+                DepthBuffer::Texture(texture)
+            }
+        });
+
+        let status = gl.check_framebuffer_status(Buffers::Framebuffer);
+        gl.unbind_framebuffer(Buffers::Framebuffer);
+        let target = RenderTarget {
+            gl,
+            framebuffer,
+            color,
+            depth,
+            width: self.width,
+            height: self.height,
+        };
+        match status {
+            FramebufferStatus::Complete => Ok(target),
+            _ => Err(status),
+        }
+    }
+}
+
+/// A framebuffer owning its attachments.
+///
+/// The OpenGL objects are deleted when the render target is dropped.
+pub struct RenderTarget<'a> {
+    gl: &'a GLContext,
+    framebuffer: WebGLFrameBuffer,
+    color: Option<WebGLTexture>,
+    depth: Option<DepthBuffer>,
+    width: u16,
+    height: u16,
+}
+
+impl<'a> RenderTarget<'a> {
+    /// the framebuffer object
+    pub fn framebuffer(&self) -> &WebGLFrameBuffer {
+        &self.framebuffer
+    }
+
+    /// the texture receiving the colors, if any
+    pub fn color_texture(&self) -> Option<&WebGLTexture> {
+        self.color.as_ref()
+    }
+
+    /// the depth attachment, if any
+    pub fn depth_buffer(&self) -> Option<&DepthBuffer> {
+        self.depth.as_ref()
+    }
+
+    /// width of the attachments in pixels
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    /// height of the attachments in pixels
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    /// render into this target
+    pub fn bind(&self) {
+        self.gl
+            .bind_framebuffer(Buffers::Framebuffer, &self.framebuffer);
+    }
+
+    /// render into the default framebuffer again
+    pub fn unbind(&self) {
+        self.gl.unbind_framebuffer(Buffers::Framebuffer);
+    }
+}
+
+impl<'a> Drop for RenderTarget<'a> {
+    fn drop(&mut self) {
+        self.gl.delete_framebuffer(&self.framebuffer);
+        if let Some(texture) = &self.color {
+            self.gl.delete_texture(texture);
+        }
+        match &self.depth {
+            Some(DepthBuffer::Renderbuffer(renderbuffer)) => {
+                self.gl.delete_renderbuffer(renderbuffer)
+            }
+            Some(DepthBuffer::Texture(texture)) => self.gl.delete_texture(texture),
+            None => (),
+        }
+    }
+}
+
+impl GLContext {
+    /// start building an offscreen [`RenderTarget`]
+    pub fn framebuffer_builder(&self) -> FramebufferBuilder<'_> {
+        FramebufferBuilder::new(self)
+    }
+}
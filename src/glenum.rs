@@ -20,6 +20,27 @@ pub enum ShaderKind {
     Fragment = 0x8B30,
     /// Passed to createShader to define a vertex shader
     Vertex = 0x8B31,
+    /// Passed to createShader to define a geometry shader (native only)
+    Geometry = 0x8DD9,
+    /// Passed to createShader to define a tessellation control shader (native only)
+    TessControl = 0x8E88,
+    /// Passed to createShader to define a tessellation evaluation shader (native only)
+    TessEvaluation = 0x8E87,
+}
+
+impl TryFrom<u32> for ShaderKind {
+    type Error = u32;
+
+    fn try_from(value: u32) -> Result<Self, u32> {
+        Ok(match value {
+            0x8B30 => ShaderKind::Fragment,
+            0x8B31 => ShaderKind::Vertex,
+            0x8DD9 => ShaderKind::Geometry,
+            0x8E88 => ShaderKind::TessControl,
+            0x8E87 => ShaderKind::TessEvaluation,
+            _ => return Err(value),
+        })
+    }
 }
 
 /// Constants passed to WebGLRenderingContext.createShader()
@@ -53,12 +74,16 @@ pub enum ShaderParameter {
     MaxTextureImageUnits = 0x8872,
     ///
     MaxFragmentUniformVectors = 0x8DFD,
-    ///
+    /// Passed to getShaderParameter to get the [`ShaderKind`] of a shader.
     ShaderType = 0x8B4F,
     ///
     ShadingLanguageVersion = 0x8B8C,
     ///
     CurrentProgram = 0x8B8D,
+    /// Passed to getProgramParameter to get the number of uniform blocks active in a program (WebGL 2.0).
+    ActiveUniformBlocks = 0x8A36,
+    /// Passed to getProgramParameter to get the number of varyings captured by transform feedback (WebGL 2.0).
+    TransformFeedbackVaryings = 0x8C83,
 }
 
 /// Passed to bindBuffer or bufferData to specify the type of buffer being used.
@@ -68,8 +93,65 @@ pub enum BufferKind {
     Array = 0x8892,
     /// to store vertex array indices
     ElementArray = 0x8893,
+    /// to store uniform blocks (WebGL 2.0)
+    UniformBuffer = 0x8A11,
+    /// to capture transform feedback output (WebGL 2.0)
+    TransformFeedbackBuffer = 0x8C8E,
+    /// to receive pixels read back with readPixels (WebGL 2.0)
+    PixelPackBuffer = 0x88EB,
+    /// to source pixels for texture uploads (WebGL 2.0)
+    PixelUnpackBuffer = 0x88EC,
 }
 
+/// Passed to getActiveUniformBlockParameter to query a uniform block of a program (WebGL 2.0).
+#[derive(Debug, Clone, Copy)]
+pub enum UniformBlockParam {
+    /// the uniform buffer binding point of the block
+    Binding = 0x8A3F,
+    /// the minimum size of the buffer backing the block, in bytes
+    DataSize = 0x8A40,
+    /// the length of the block name, including the null terminator
+    NameLength = 0x8A41,
+    /// the number of active uniforms in the block
+    ActiveUniforms = 0x8A42,
+    /// the indices of the active uniforms in the block
+    ActiveUniformIndices = 0x8A43,
+    /// whether the block is used by the vertex shader
+    ReferencedByVertexShader = 0x8A44,
+    /// whether the block is used by the fragment shader
+    ReferencedByFragmentShader = 0x8A46,
+}
+
+/// Passed to getIndexedParameter to query an indexed buffer binding point.
+#[derive(Debug, Clone, Copy)]
+pub enum IndexedParam {
+    /// the name of the buffer bound to the binding point
+    Binding,
+    /// the offset of the bound range, in bytes
+    Start,
+    /// the size of the bound range, in bytes. 0 when the whole buffer is bound
+    Size,
+}
+
+impl IndexedParam {
+    /// the GL enum querying this parameter for the given indexed buffer kind,
+    /// None for the kinds without indexed binding points
+    pub(crate) fn pname(self, target: BufferKind) -> Option<u32> {
+        match (target, self) {
+            (BufferKind::UniformBuffer, IndexedParam::Binding) => Some(0x8A28),
+            (BufferKind::UniformBuffer, IndexedParam::Start) => Some(0x8A29),
+            (BufferKind::UniformBuffer, IndexedParam::Size) => Some(0x8A2A),
+            (BufferKind::TransformFeedbackBuffer, IndexedParam::Binding) => Some(0x8C8F),
+            (BufferKind::TransformFeedbackBuffer, IndexedParam::Start) => Some(0x8C84),
+            (BufferKind::TransformFeedbackBuffer, IndexedParam::Size) => Some(0x8C85),
+            _ => None,
+        }
+    }
+}
+
+/// Passed to bufferData as a hint about how the buffer contents will be used.
+///
+/// The `*Read` and `*Copy` variants are WebGL 2.0 only. On WebGL 1.0 they fall back to the matching `*Draw` hint.
 #[derive(Debug, Clone, Copy)]
 pub enum DrawMode {
     /// Passed to bufferData as a hint about whether the contents of the buffer are likely to be used often and not change often.
@@ -78,8 +160,21 @@ pub enum DrawMode {
     Dynamic = 0x88E8,
     /// Passed to bufferData as a hint about whether the contents of the buffer are likely to not be used often.
     Stream = 0x88E0,
+    /// Contents are specified once by reading data from OpenGL, and queried many times by the application.
+    StaticRead = 0x88E5,
+    /// Contents are specified once by reading data from OpenGL, and used many times as the source for drawing.
+    StaticCopy = 0x88E6,
+    /// Contents are respecified repeatedly by reading data from OpenGL, and queried many times by the application.
+    DynamicRead = 0x88E9,
+    /// Contents are respecified repeatedly by reading data from OpenGL, and used many times as the source for drawing.
+    DynamicCopy = 0x88EA,
+    /// Contents are specified once by reading data from OpenGL, and queried at most a few times by the application.
+    StreamRead = 0x88E1,
+    /// Contents are specified once by reading data from OpenGL, and used at most a few times as the source for drawing.
+    StreamCopy = 0x88E2,
 }
 
+/// Passed to getBufferParameter to specify what information to return.
 #[derive(Debug, Clone, Copy)]
 pub enum BufferParameter {
     /// Passed to getBufferParameter to get a buffer's size.
@@ -99,6 +194,36 @@ pub enum DataType {
     Float = 0x1406,
 }
 
+impl DataType {
+    /// size in bytes of a value of this type
+    pub fn size(self) -> usize {
+        match self {
+            DataType::I8 | DataType::U8 => 1,
+            DataType::I16 | DataType::U16 => 2,
+            DataType::I32 | DataType::U32 | DataType::Float => 4,
+        }
+    }
+}
+
+/// Rust types that can be used as vertex attribute components or indices, with their matching [`DataType`]
+pub trait GlPrimitive {
+    /// the matching OpenGL type
+    const GL_TYPE: DataType;
+    /// size in bytes
+    const SIZE: usize;
+}
+
+macro_rules! impl_gl_primitive {
+    ($($t:ty => $kind:ident),*) => {
+        $(impl GlPrimitive for $t {
+            const GL_TYPE: DataType = DataType::$kind;
+            const SIZE: usize = std::mem::size_of::<$t>();
+        })*
+    };
+}
+
+impl_gl_primitive!(i8 => I8, u8 => U8, i16 => I16, u16 => U16, i32 => I32, u32 => U32, f32 => Float);
+
 #[derive(Debug, Clone, Copy)]
 pub enum Flag {
     /// Passed to enable/disable to turn on/off blending. Can also be used with getParameter to find the current blending method.
@@ -117,6 +242,20 @@ pub enum Flag {
     ScissorTest = 0x0C11,
     /// Passed to enable/disable to turn on/off the stencil test. Can also be used with getParameter to query the stencil test.
     StencilTest = 0x0B90,
+    /// Passed to enable/disable to restart primitives at the maximum value of the index type (OpenGL 4.3 / ES 3.0).
+    /// WebGL 2.0 always behaves as if it was enabled.
+    PrimitiveRestartFixedIndex = 0x8D69,
+    /// Passed to enable/disable to restart primitives at the index set with `primitive_restart_index` (native only).
+    PrimitiveRestart = 0x8F9D,
+    /// Passed to enable/disable to discard primitives before rasterization, when only transform feedback is needed (WebGL 2.0).
+    RasterizerDiscard = 0x8C89,
+    /// Passed to enable/disable to run the fragment shader per sample, see `min_sample_shading` (native only).
+    SampleShading = 0x8C36,
+    /// Passed to enable/disable to mask the covered samples with `sample_mask` (native only).
+    SampleMask = 0x8E51,
+    /// Passed to enable/disable to discard fragments outside of the range set with `depth_bounds`
+    /// (native only, requires GL_EXT_depth_bounds_test).
+    DepthBoundsTest = 0x8890,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -129,6 +268,31 @@ pub enum BufferBit {
     Color = 0x00004000,
 }
 
+/// Flags for `buffer_storage` and `map_buffer_range`, combined as a bitfield with `as u32` and `|`.
+#[derive(Debug, Clone, Copy)]
+pub enum BufferAccessBit {
+    /// The mapped range may be read by the client.
+    MapRead = 0x0001,
+    /// The mapped range may be written by the client.
+    MapWrite = 0x0002,
+    /// The previous content of the mapped range may be discarded.
+    MapInvalidateRange = 0x0004,
+    /// The previous content of the whole buffer may be discarded.
+    MapInvalidateBuffer = 0x0008,
+    /// Modified parts of the mapped range must be flushed explicitly.
+    MapFlushExplicit = 0x0010,
+    /// OpenGL should not synchronize pending operations on the buffer before mapping it.
+    MapUnsynchronized = 0x0020,
+    /// The buffer may stay mapped while it is used by OpenGL commands.
+    MapPersistent = 0x0040,
+    /// Writes to a persistently mapped buffer are visible to OpenGL without explicit barrier.
+    MapCoherent = 0x0080,
+    /// The content of the storage may be updated with `buffer_sub_data`.
+    DynamicStorage = 0x0100,
+    /// Hint that the storage should live in client memory.
+    ClientStorage = 0x0200,
+}
+
 /// Passed to drawElements or drawArrays to draw primitives.
 #[derive(Debug, Clone, Copy)]
 pub enum Primitives {
@@ -146,6 +310,9 @@ pub enum Primitives {
     TriangleStrip = 0x0005,
     /// Passed to drawElements or drawArrays to draw a connected group of triangles. Each vertex connects to the previous and the first vertex in the fan.
     TriangleFan = 0x0006,
+    /// Passed to drawElements or drawArrays to draw patches processed by tessellation shaders (native only).
+    /// The number of vertices per patch is set with `patch_parameter_vertices`.
+    Patches = 0x000E,
 }
 
 /// Constants passed to WebGLRenderingContext.blendFunc() or WebGLRenderingContext.blendFuncSeparate() to specify the blending mode (for both, RBG and alpha, or separately).
@@ -183,6 +350,31 @@ pub enum BlendMode {
     OneMinusConstantAlpha = 0x8004,
 }
 
+impl TryFrom<u32> for BlendMode {
+    type Error = u32;
+
+    fn try_from(value: u32) -> Result<Self, u32> {
+        Ok(match value {
+            0 => BlendMode::Zero,
+            1 => BlendMode::One,
+            0x0300 => BlendMode::SrcColor,
+            0x0301 => BlendMode::OneMinusSrcColor,
+            0x0302 => BlendMode::SrcAlpha,
+            0x0303 => BlendMode::OneMinusSrcAlpha,
+            0x0304 => BlendMode::DstAlpha,
+            0x0305 => BlendMode::OneMinusDstAlpha,
+            0x0306 => BlendMode::DstColor,
+            0x0307 => BlendMode::OneMinusDstColor,
+            0x0308 => BlendMode::SrcAlphaSaturate,
+            0x8001 => BlendMode::ConstantColor,
+            0x8002 => BlendMode::OneMinusConstantColor,
+            0x8003 => BlendMode::ConstantAlpha,
+            0x8004 => BlendMode::OneMinusConstantAlpha,
+            _ => return Err(value),
+        })
+    }
+}
+
 /// Constants passed to WebGLRenderingContext.blendEquation()
 /// or WebGLRenderingContext.blendEquationSeparate() to control
 /// how the blending is calculated (for both, RBG and alpha, or separately).
@@ -279,6 +471,10 @@ pub enum Parameter {
     PackAlignment = 0x0D05,
     ///
     MaxTextureSize = 0x0D33,
+    /// Maximum number of texture units usable by all shader stages together.
+    MaxCombinedTextureImageUnits = 0x8B4D,
+    /// Returns the active texture unit, as `TEXTURE0 + unit`.
+    ActiveTexture = 0x84E0,
     ///
     MaxViewportDims = 0x0D3A,
     ///
@@ -309,6 +505,8 @@ pub enum Parameter {
     SampleCoverageValue = 0x80AA,
     ///
     SampleCoverageInvert = 0x80AB,
+    /// Passed to getParameter to get the number of available compressed texture formats.
+    NumCompressedTextureFormats = 0x86A2,
     ///
     CompressedTextureFormats = 0x86A3,
     ///
@@ -391,9 +589,9 @@ pub enum FrontFaceDirection {
     CCW = 0x0901,
 }
 
-/// Constants passed to WebGLRenderingContext.depthFunc().
+/// Comparison functions passed to WebGLRenderingContext.depthFunc() and stencilFunc().
 #[derive(Debug, Clone, Copy)]
-pub enum DepthTest {
+pub enum CompareFunc {
     /// Passed to depthFunction or stencilFunction to specify depth or stencil tests will never pass. i.e. Nothing will be drawn.
     Never = 0x0200,
     /// Passed to depthFunction or stencilFunction to specify depth or stencil tests will always pass. i.e. Pixels will be drawn in the order they are drawn.
@@ -412,6 +610,27 @@ pub enum DepthTest {
     Notequal = 0x0205,
 }
 
+/// Former name of [`CompareFunc`], kept for source compatibility.
+pub type DepthTest = CompareFunc;
+
+impl TryFrom<u32> for CompareFunc {
+    type Error = u32;
+
+    fn try_from(value: u32) -> Result<Self, u32> {
+        Ok(match value {
+            0x0200 => CompareFunc::Never,
+            0x0207 => CompareFunc::Always,
+            0x0201 => CompareFunc::Less,
+            0x0202 => CompareFunc::Equal,
+            0x0203 => CompareFunc::Lequal,
+            0x0204 => CompareFunc::Greater,
+            0x0206 => CompareFunc::Gequal,
+            0x0205 => CompareFunc::Notequal,
+            _ => return Err(value),
+        })
+    }
+}
+
 /// Constants passed to WebGLRenderingContext.stencilFunc().
 #[derive(Debug, Clone, Copy)]
 pub enum StencilTest {
@@ -468,17 +687,28 @@ pub enum PixelType {
     ///
     UnsignedInt = 0x1405,
 
-    ///
-    UnsignedInt24 = 0x84FA,
+    /// Packed 24 bits depth and 8 bits stencil, for [`PixelFormat::DepthStencil`]
+    UnsignedInt248 = 0x84FA,
 
     ///
     Float = 0x1406,
+    /// 16 bits float components (WebGL 2.0 / OpenGL 3.0)
+    HalfFloat = 0x140B,
+}
+
+impl PixelType {
+    /// former name of [`PixelType::UnsignedInt248`]
+    #[deprecated(note = "use PixelType::UnsignedInt248")]
+    #[allow(non_upper_case_globals)]
+    pub const UnsignedInt24: PixelType = PixelType::UnsignedInt248;
 }
 
 #[derive(Debug, Clone, Copy)]
 pub enum PixelFormat {
     ///
     DepthComponent = 0x1902,
+    /// Packed depth and stencil, used with [`PixelType::UnsignedInt248`]
+    DepthStencil = 0x84F9,
     ///
     Alpha = 0x1906,
     ///
@@ -489,6 +719,40 @@ pub enum PixelFormat {
     Luminance = 0x1909,
     ///
     LuminanceAlpha = 0x190A,
+    /// Single red channel (WebGL 2.0 / OpenGL 3.0).
+    Red = 0x1903,
+    /// Red and green channels (WebGL 2.0 / OpenGL 3.0).
+    Rg = 0x8227,
+    /// Sized internal format: 8 bits red channel.
+    R8 = 0x8229,
+    /// Sized internal format: 8 bits red and green channels.
+    Rg8 = 0x822B,
+    /// Sized internal format: 8 bits per channel RGB.
+    Rgb8 = 0x8051,
+    /// Sized internal format: 8 bits per channel RGBA.
+    Rgba8 = 0x8058,
+    /// Sized internal format: 8 bits per channel sRGB color with linear alpha.
+    Srgb8Alpha8 = 0x8C43,
+    /// Sized internal format: 16 bits float red channel.
+    R16f = 0x822D,
+    /// Sized internal format: 16 bits float red and green channels.
+    Rg16f = 0x822F,
+    /// Sized internal format: 16 bits float per channel RGBA.
+    Rgba16f = 0x881A,
+    /// Sized internal format: 32 bits float red channel.
+    R32f = 0x822E,
+    /// Sized internal format: 32 bits float red and green channels.
+    Rg32f = 0x8230,
+    /// Sized internal format: 32 bits float per channel RGBA.
+    Rgba32f = 0x8814,
+    /// Sized internal format: 16 bits depth.
+    DepthComponent16 = 0x81A5,
+    /// Sized internal format: 24 bits depth.
+    DepthComponent24 = 0x81A6,
+    /// Sized internal format: 32 bits float depth.
+    DepthComponent32f = 0x8CAC,
+    /// Sized internal format: 24 bits depth and 8 bits stencil.
+    Depth24Stencil8 = 0x88F0,
 }
 
 /// Constants passed to WebGLRenderingContext.hint()
@@ -529,6 +793,29 @@ pub enum TextureParameter {
 
     /// WebGL 2.0 only
     TextureWrapR = 32882,
+    /// Lowest defined mipmap level. WebGL 2.0 only
+    TextureBaseLevel = 0x813C,
+    /// Highest defined mipmap level. WebGL 2.0 only
+    TextureMaxLevel = 0x813D,
+    /// Minimum level of detail used when sampling. WebGL 2.0 only
+    TextureMinLod = 0x813A,
+    /// Maximum level of detail used when sampling. WebGL 2.0 only
+    TextureMaxLod = 0x813B,
+    /// Bias added to the level of detail used when sampling. Native only
+    TextureLodBias = 0x8501,
+    /// Whether sampling a depth texture compares it to a reference value, see [`TextureCompareMode`]. WebGL 2.0 only
+    TextureCompareMode = 0x884C,
+    /// The [`CompareFunc`] used when [`TextureParameter::TextureCompareMode`] is enabled. WebGL 2.0 only
+    TextureCompareFunc = 0x884D,
+}
+
+/// Values of the [`TextureParameter::TextureCompareMode`] texture parameter.
+#[derive(Debug, Clone, Copy)]
+pub enum TextureCompareMode {
+    /// Sampling returns the depth value
+    None = 0,
+    /// Sampling returns the result of the comparison with the reference value, for `sampler2DShadow`
+    CompareRefToTexture = 0x884E,
 }
 
 /// WebGLRenderingContext.texImage2D() "target" parameter
@@ -550,6 +837,48 @@ pub enum TextureBindPoint {
     TextureCubeMapNegativeZ = 0x851A,
 }
 
+/// A face of a cube map texture, see [`GLContext::tex_image2d_cube_face`](crate::GLContext::tex_image2d_cube_face)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CubeFace {
+    /// the face looking toward +X
+    PositiveX,
+    /// the face looking toward -X
+    NegativeX,
+    /// the face looking toward +Y
+    PositiveY,
+    /// the face looking toward -Y
+    NegativeY,
+    /// the face looking toward +Z
+    PositiveZ,
+    /// the face looking toward -Z
+    NegativeZ,
+}
+
+impl CubeFace {
+    /// the six faces, in the order of their OpenGL constants
+    pub const ALL: [CubeFace; 6] = [
+        CubeFace::PositiveX,
+        CubeFace::NegativeX,
+        CubeFace::PositiveY,
+        CubeFace::NegativeY,
+        CubeFace::PositiveZ,
+        CubeFace::NegativeZ,
+    ];
+}
+
+impl From<CubeFace> for TextureBindPoint {
+    fn from(face: CubeFace) -> Self {
+        match face {
+            CubeFace::PositiveX => TextureBindPoint::TextureCubeMapPositiveX,
+            CubeFace::NegativeX => TextureBindPoint::TextureCubeMapNegativeX,
+            CubeFace::PositiveY => TextureBindPoint::TextureCubeMapPositiveY,
+            CubeFace::NegativeY => TextureBindPoint::TextureCubeMapNegativeY,
+            CubeFace::PositiveZ => TextureBindPoint::TextureCubeMapPositiveZ,
+            CubeFace::NegativeZ => TextureBindPoint::TextureCubeMapNegativeZ,
+        }
+    }
+}
+
 /// WebGLRenderingContext.texParameter[fi]() "param" parameter
 #[derive(Debug, Clone, Copy)]
 pub enum TextureMagFilter {
@@ -634,13 +963,13 @@ pub enum Buffers {
     FramebufferAttachmentTextureLevel = 0x8CD2,
     ///
     FramebufferAttachmentTextureCubeMapFace = 0x8CD3,
-    ///
+    /// Attachment point for the first color buffer of a framebuffer.
     ColorAttachment0 = 0x8CE0,
-    ///
+    /// Attachment point for the depth buffer of a framebuffer, used to render into depth textures (shadow maps).
     DepthAttachment = 0x8D00,
-    ///
+    /// Attachment point for the stencil buffer of a framebuffer.
     StencilAttachment = 0x8D20,
-    ///
+    /// Attachment point for a combined depth and stencil buffer of a framebuffer.
     DepthStencilAttachment = 0x821A,
     ///
     None = 0,
@@ -664,6 +993,51 @@ pub enum Buffers {
     InvalidFramebufferOperation = 0x0506,
 }
 
+/// Result of checkFramebufferStatus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FramebufferStatus {
+    /// The framebuffer can be rendered to (0x8CD5).
+    Complete,
+    /// An attachment is missing data or has an unrenderable format (0x8CD6).
+    IncompleteAttachment,
+    /// The framebuffer has no attachment (0x8CD7).
+    IncompleteMissingAttachment,
+    /// The attachments don't have the same size, WebGL 1.0 (0x8CD9).
+    IncompleteDimensions,
+    /// A draw buffer has no attachment, desktop OpenGL (0x8CDB).
+    IncompleteDrawBuffer,
+    /// The read buffer has no attachment, desktop OpenGL (0x8CDC).
+    IncompleteReadBuffer,
+    /// The attachments don't have the same number of samples (0x8D56).
+    IncompleteMultisample,
+    /// Layered and non layered attachments are mixed, desktop OpenGL (0x8DA8).
+    IncompleteLayerTargets,
+    /// The combination of attachment formats is not supported by the implementation (0x8CDD).
+    Unsupported,
+    /// The default framebuffer is bound but does not exist (0x8219).
+    Undefined,
+    /// Any other value, like 0 when the status query itself failed.
+    Unknown(u32),
+}
+
+impl From<u32> for FramebufferStatus {
+    fn from(value: u32) -> Self {
+        match value {
+            0x8CD5 => FramebufferStatus::Complete,
+            0x8CD6 => FramebufferStatus::IncompleteAttachment,
+            0x8CD7 => FramebufferStatus::IncompleteMissingAttachment,
+            0x8CD9 => FramebufferStatus::IncompleteDimensions,
+            0x8CDB => FramebufferStatus::IncompleteDrawBuffer,
+            0x8CDC => FramebufferStatus::IncompleteReadBuffer,
+            0x8D56 => FramebufferStatus::IncompleteMultisample,
+            0x8DA8 => FramebufferStatus::IncompleteLayerTargets,
+            0x8CDD => FramebufferStatus::Unsupported,
+            0x8219 => FramebufferStatus::Undefined,
+            _ => FramebufferStatus::Unknown(value),
+        }
+    }
+}
+
 /// Constants passed to WebGLRenderingContext.hint()
 #[derive(Debug, Clone, Copy)]
 pub enum PixelStorageMode {
@@ -679,6 +1053,13 @@ pub enum PixelStorageMode {
     /// Unpacking of pixel data from memory
     /// Can be 1, 2, 4, 8 defaults to 4
     UnpackAlignment = 0x0CF5,
+    /// Number of pixels in a row of the source image, when uploading a part of it.
+    /// 0, the default, means the width of the upload (WebGL 2.0)
+    UnpackRowLength = 0x0CF2,
+    /// Number of rows skipped at the start of the source image (WebGL 2.0)
+    UnpackSkipRows = 0x0CF3,
+    /// Number of pixels skipped at the start of each row of the source image (WebGL 2.0)
+    UnpackSkipPixels = 0x0CF4,
 }
 
 ///
@@ -747,12 +1128,37 @@ pub enum TextureCompression {
     /// It also provides a 4:1 compression,
     /// but differs to the DXT3 compression in how the alpha compression is done.
     RgbaDxt5 = 0x83F3,
+    /// One-channel (red) unsigned format compression using EAC.
+    R11Eac = 0x9270,
+    /// Two-channel (red and green) unsigned format compression using EAC.
+    Rg11Eac = 0x9272,
+    /// An ETC2-compressed image in an RGB image format.
+    RgbEtc2 = 0x9274,
+    /// An ETC2-compressed image in an RGBA image format with a simple on/off alpha value.
+    RgbPunchthroughAlpha1Etc2 = 0x9276,
+    /// An ETC2-compressed image in an RGBA image format, alpha being compressed using EAC.
+    RgbaEtc2Eac = 0x9278,
+    /// An ASTC-compressed RGBA image using 4x4 texel blocks (8 bits per pixel).
+    RgbaAstc4x4 = 0x93B0,
+    /// An ASTC-compressed RGBA image using 5x5 texel blocks (5.12 bits per pixel).
+    RgbaAstc5x5 = 0x93B2,
+    /// An ASTC-compressed RGBA image using 6x6 texel blocks (3.56 bits per pixel).
+    RgbaAstc6x6 = 0x93B4,
+    /// An ASTC-compressed RGBA image using 8x6 texel blocks (2.67 bits per pixel).
+    RgbaAstc8x6 = 0x93B6,
+    /// An ASTC-compressed RGBA image using 8x8 texel blocks (2 bits per pixel).
+    RgbaAstc8x8 = 0x93B7,
+    /// An ASTC-compressed RGBA image using 10x10 texel blocks (1.28 bits per pixel).
+    RgbaAstc10x10 = 0x93BB,
+    /// An ASTC-compressed RGBA image using 12x12 texel blocks (0.89 bits per pixel).
+    RgbaAstc12x12 = 0x93BD,
 }
 
 ///
 #[derive(Debug, Clone, Copy)]
 pub enum ColorBuffer {
     None = 0,
+    Front = 0x0404,
     Back = 0x0405,
     ColorAttachment0 = 0x8CE0,
     ColorAttachment1 = 0x8CE1,
@@ -764,3 +1170,34 @@ pub enum ColorBuffer {
     ColorAttachment7 = 0x8CE7,
     ColorAttachment8 = 0x8CE8,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn color_attachments_follow_color_attachment0() {
+        const COLOR_ATTACHMENT0: u32 = 0x8CE0;
+        let attachments = [
+            ColorBuffer::ColorAttachment0,
+            ColorBuffer::ColorAttachment1,
+            ColorBuffer::ColorAttachment2,
+            ColorBuffer::ColorAttachment3,
+            ColorBuffer::ColorAttachment4,
+            ColorBuffer::ColorAttachment5,
+            ColorBuffer::ColorAttachment6,
+            ColorBuffer::ColorAttachment7,
+            ColorBuffer::ColorAttachment8,
+        ];
+        for (n, attachment) in attachments.iter().enumerate() {
+            assert_eq!(*attachment as u32, COLOR_ATTACHMENT0 + n as u32);
+        }
+    }
+
+    #[test]
+    fn default_color_buffers() {
+        assert_eq!(ColorBuffer::None as u32, 0);
+        assert_eq!(ColorBuffer::Front as u32, 0x0404);
+        assert_eq!(ColorBuffer::Back as u32, 0x0405);
+    }
+}
@@ -0,0 +1,48 @@
+//! Pixels read back from a framebuffer.
+//!
+//! ```ignore
+//! let mut image = gl.capture_framebuffer(0, 0, 800, 600, uni_gl::PixelFormat::Rgba, uni_gl::PixelType::UnsignedByte);
+//! image.flip_vertically();
+//! ```
+
+use crate::glenum::*;
+use crate::util::bytes_per_pixel;
+
+/// A block of tightly packed pixels, returned by [`GLContext::capture_framebuffer`](crate::GLContext::capture_framebuffer).
+///
+/// Rows are stored bottom to top, like OpenGL does, until [`Image::flip_vertically`] is called.
+#[derive(Debug, Clone)]
+pub struct Image {
+    /// width in pixels
+    pub width: u32,
+    /// height in pixels
+    pub height: u32,
+    /// components of each pixel
+    pub format: PixelFormat,
+    /// type of each component
+    pub kind: PixelType,
+    /// the pixels, without padding between rows
+    pub data: Vec<u8>,
+}
+
+impl Image {
+    /// number of bytes used by a pixel
+    pub fn bytes_per_pixel(&self) -> usize {
+        bytes_per_pixel(self.format, self.kind)
+    }
+
+    /// number of bytes used by a row of pixels
+    pub fn row_len(&self) -> usize {
+        self.width as usize * self.bytes_per_pixel()
+    }
+
+    /// swap the rows, to go from the bottom-left origin of OpenGL to the top-left origin of image files
+    pub fn flip_vertically(&mut self) {
+        let row_len = self.row_len();
+        let height = self.height as usize;
+        for y in 0..height / 2 {
+            let (top, bottom) = self.data.split_at_mut((height - 1 - y) * row_len);
+            top[y * row_len..(y + 1) * row_len].swap_with_slice(&mut bottom[..row_len]);
+        }
+    }
+}
@@ -1,16 +1,35 @@
 #![recursion_limit = "512"]
 
-#[cfg(not(target_arch = "wasm32"))]
+#[cfg(all(
+    not(target_arch = "wasm32"),
+    feature = "gl-backend",
+    not(feature = "glow-backend")
+))]
 extern crate gl;
 
+#[cfg(all(
+    not(target_arch = "wasm32"),
+    not(feature = "gl-backend"),
+    not(feature = "glow-backend")
+))]
+compile_error!("uni-gl needs the gl-backend or the glow-backend feature on native targets");
+
 #[cfg(target_arch = "wasm32")]
 #[path = "webgl.rs"]
 pub mod webgl;
 
-#[cfg(not(target_arch = "wasm32"))]
+#[cfg(all(
+    not(target_arch = "wasm32"),
+    feature = "gl-backend",
+    not(feature = "glow-backend")
+))]
 #[path = "webgl_native.rs"]
 mod webgl;
 
+#[cfg(all(not(target_arch = "wasm32"), feature = "glow-backend"))]
+#[path = "webgl_glow.rs"]
+mod webgl;
+
 #[cfg(not(target_arch = "wasm32"))]
 /// whether current OpenGL context is OpenGL ES (Embedded System)
 pub const IS_GL_ES: bool = false;
@@ -18,11 +37,41 @@ pub const IS_GL_ES: bool = false;
 #[cfg(target_arch = "wasm32")]
 pub const IS_GL_ES: bool = true;
 
+#[cfg(feature = "helpers")]
+mod framebuffer;
 mod glenum;
+mod image;
+#[cfg(feature = "helpers")]
+mod mesh;
+#[cfg(any(target_arch = "wasm32", test))]
+mod objects;
+#[cfg(feature = "helpers")]
+mod shader_builder;
+mod shared;
+#[cfg(feature = "helpers")]
+mod uniforms;
+mod util;
 
+#[cfg(feature = "helpers")]
+pub use framebuffer::*;
 pub use glenum::*;
+pub use image::Image;
+#[cfg(feature = "helpers")]
+pub use mesh::*;
+#[cfg(feature = "helpers")]
+pub use shader_builder::*;
+pub use shared::ActiveTextureGuard;
+#[cfg(feature = "helpers")]
+pub use uniforms::*;
+pub use util::*;
+#[cfg(target_arch = "wasm32")]
+pub use webgl::ContextAttributes;
 pub use webgl::{GLContext, WebGLContext};
 
+/// The context and the handles to the objects it creates.
+///
+/// Handles only hold the reference of their object, cloning one does not duplicate the
+/// object on the GPU. Clones refer to the same object, which must be deleted once.
 pub mod common {
     use std::ops::{Deref, DerefMut};
 
@@ -48,7 +97,7 @@ pub mod common {
         }
     }
 
-    #[derive(Debug)]
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
     /// an OpenGL buffer created with [`GLContext::create_buffer`].
     ///
     /// Buffers are used to store vertex attributes
@@ -62,7 +111,7 @@ pub mod common {
         }
     }
 
-    #[derive(Debug)]
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
     /// an OpenGL shader created with [`GLContext::create_shader`]
     pub struct WebGLShader(pub Reference);
     impl Deref for WebGLShader {
@@ -72,7 +121,7 @@ pub mod common {
         }
     }
 
-    #[derive(Debug, PartialEq)]
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
     /// an OpenGL shader created with [`GLContext::create_shader`].
     ///
     /// There are two kinds of shaders ([`ShaderKind`]) : vertex and fragment
@@ -84,7 +133,7 @@ pub mod common {
         }
     }
 
-    #[derive(Debug, PartialEq)]
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
     /// an OpenGL program created with [`GLContext::create_program`].
     ///
     /// It is built with a vertex shader and a fragment shader.
@@ -96,7 +145,7 @@ pub mod common {
         }
     }
 
-    #[derive(Debug)]
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
     /// an OpenGL vertex array object created with [`GLContext::create_vertex_array`].
     ///
     /// Vertex array objects store all the state needed to supply vertex data.
@@ -108,7 +157,7 @@ pub mod common {
         }
     }
 
-    #[derive(Debug, PartialEq)]
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
     /// the reference to a uniform (global GLSL variable) inside a shader, obtained with [`GLContext::get_uniform_location`].
     pub struct WebGLUniformLocation {
         pub reference: Reference,
@@ -121,7 +170,7 @@ pub mod common {
         }
     }
 
-    #[derive(Debug)]
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
     /// an OpenGL Framebuffer created with [`GLContext::create_framebuffer`].
     ///
     /// This is a special type of buffer that can be used as destination for rendering.
@@ -133,6 +182,153 @@ pub mod common {
         }
     }
 
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    /// an OpenGL Renderbuffer created with [`GLContext::create_renderbuffer`].
+    ///
+    /// This is an image that can be attached to a framebuffer but not sampled in shaders.
+    pub struct WebGLRenderBuffer(pub Reference);
+    impl Deref for WebGLRenderBuffer {
+        type Target = Reference;
+        fn deref(&self) -> &Self::Target {
+            &self.0
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+    /// a rectangle in window coordinates, used by [`GLContext::viewport_rect`], [`GLContext::scissor_rect`],
+    /// [`GLContext::viewport_indexed`] and [`GLContext::viewport_array`].
+    pub struct Rect {
+        /// left side
+        pub x: i32,
+        /// bottom side
+        pub y: i32,
+        /// width in pixels
+        pub w: u32,
+        /// height in pixels
+        pub h: u32,
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    /// a texture image and the corner of a region of it, used by [`GLContext::copy_image_sub_data`].
+    pub struct ImageRegion<'a> {
+        pub texture: &'a WebGLTexture,
+        pub target: super::TextureBindPoint,
+        /// mipmap level
+        pub level: u8,
+        /// x, y and z, or layer, of the first texel
+        pub offset: [i32; 3],
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    /// a recorded draw call, submitted with [`GLContext::draw`].
+    pub enum DrawCommand {
+        /// see [`GLContext::draw_arrays`]
+        Arrays {
+            mode: super::Primitives,
+            count: usize,
+        },
+        /// see [`GLContext::draw_elements`]
+        Elements {
+            mode: super::Primitives,
+            count: usize,
+            kind: super::DataType,
+            offset: u32,
+        },
+        /// see [`GLContext::draw_arrays_instanced`]
+        ArraysInstanced {
+            mode: super::Primitives,
+            count: usize,
+            instances: usize,
+        },
+        /// see [`GLContext::draw_elements_instanced`]
+        ElementsInstanced {
+            mode: super::Primitives,
+            count: usize,
+            kind: super::DataType,
+            offset: u32,
+            instances: usize,
+        },
+        /// see [`GLContext::draw_range_elements`]
+        RangeElements {
+            mode: super::Primitives,
+            start: u32,
+            end: u32,
+            count: usize,
+            kind: super::DataType,
+            offset: u32,
+        },
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    /// optional capabilities, queried with [`GLContext::supports`].
+    pub enum Feature {
+        /// [`GLContext::create_vertex_array`]
+        VertexArrayObjects,
+        /// [`GLContext::draw_arrays_instanced`] and [`GLContext::vertex_attrib_divisor`]
+        InstancedArrays,
+        /// textures with [`PixelType::Float`](super::PixelType::Float) components
+        FloatTextures,
+        /// rendering into float textures
+        ColorBufferFloat,
+        /// textures attached to [`Buffers::DepthAttachment`](super::Buffers::DepthAttachment)
+        DepthTextures,
+        /// several color attachments written with [`GLContext::draw_buffer`]
+        MultipleRenderTargets,
+        /// uniform blocks backed by [`BufferKind::UniformBuffer`](super::BufferKind::UniformBuffer)
+        UniformBufferObjects,
+        /// capturing vertex shader outputs into [`BufferKind::TransformFeedbackBuffer`](super::BufferKind::TransformFeedbackBuffer)
+        TransformFeedback,
+    }
+
+    impl Feature {
+        /// the OpenGL (major, minor) version where the feature became core
+        #[cfg(not(target_arch = "wasm32"))]
+        pub(crate) fn gl_version(self) -> (u32, u32) {
+            match self {
+                Feature::MultipleRenderTargets => (2, 0),
+                Feature::InstancedArrays | Feature::UniformBufferObjects => (3, 1),
+                _ => (3, 0),
+            }
+        }
+
+        /// the OpenGL ES (major, minor) version where the feature became core
+        #[cfg(all(not(target_arch = "wasm32"), feature = "glow-backend"))]
+        pub(crate) fn gles_version(self) -> (u32, u32) {
+            match self {
+                Feature::ColorBufferFloat => (3, 2),
+                _ => (3, 0),
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+    /// description of the GPU and driver, returned by [`GLContext::gpu_info`].
+    pub struct GpuInfo {
+        /// company responsible for the implementation
+        pub vendor: String,
+        /// name of the renderer, usually the GPU model
+        pub renderer: String,
+        /// OpenGL or WebGL version string
+        pub version: String,
+        /// GLSL version string
+        pub shading_language_version: String,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+    /// verbosity of the messages uni-gl prints by itself, set with [`GLContext::set_log_level`].
+    pub enum LogLevel {
+        /// never print anything
+        Off,
+        /// only print errors (shader compilation, program link, unsupported operations)
+        /// and warnings about a probable misuse. This is the default
+        #[default]
+        Error,
+        /// also print informations about the OpenGL context and the calls ignored by the backend
+        Info,
+        /// also print debug traces like the shader sources
+        Debug,
+    }
+
     /// Utility function to print messages to stdout (native) or the js console (web)
     pub fn print(s: &str) {
         GLContext::print(s);
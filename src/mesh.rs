@@ -0,0 +1,199 @@
+//! A vertex array object and its buffers, ready to be drawn.
+//!
+//! ```ignore
+//! let mesh = uni_gl::MeshBuilder::new(vertices.as_gl_bytes())
+//!     .attribute(position_location, uni_gl::AttributeSize::Three, uni_gl::DataType::Float)
+//!     .build(&gl);
+//! mesh.draw(uni_gl::Primitives::Triangles);
+//! ```
+
+use crate::common::*;
+use crate::glenum::*;
+use crate::GLContext;
+
+#[derive(Debug, Clone, Copy)]
+/// one vertex attribute inside an interleaved vertex buffer
+pub struct VertexAttribute {
+    /// attribute location in the shader program
+    pub location: u32,
+    /// number of components
+    pub size: AttributeSize,
+    /// type of each component
+    pub kind: DataType,
+    /// whether integer values should be normalized to [0,1] or [-1,1]
+    pub normalized: bool,
+    /// offset in bytes of this attribute inside a vertex
+    pub offset: u32,
+}
+
+/// Builds a [`Mesh`] from interleaved vertex data, an attribute layout and optional indices.
+pub struct MeshBuilder<'d> {
+    vertices: &'d [u8],
+    attributes: Vec<VertexAttribute>,
+    stride: Option<u32>,
+    indices: Option<(&'d [u8], DataType)>,
+    draw_mode: DrawMode,
+}
+
+impl<'d> MeshBuilder<'d> {
+    /// start a mesh from raw interleaved vertex data
+    pub fn new(vertices: &'d [u8]) -> MeshBuilder<'d> {
+        MeshBuilder {
+            vertices,
+            attributes: Vec::new(),
+            stride: None,
+            indices: None,
+            draw_mode: DrawMode::Static,
+        }
+    }
+
+    /// add a non normalized attribute, placed right after the previous one in the vertex
+    pub fn attribute(self, location: u32, size: AttributeSize, kind: DataType) -> Self {
+        self.attribute_normalized(location, size, kind, false)
+    }
+
+    /// add an attribute, placed right after the previous one in the vertex
+    pub fn attribute_normalized(
+        mut self,
+        location: u32,
+        size: AttributeSize,
+        kind: DataType,
+        normalized: bool,
+    ) -> Self {
+        let offset = self.packed_stride();
+        self.attributes.push(VertexAttribute {
+            location,
+            size,
+            kind,
+            normalized,
+            offset,
+        });
+        self
+    }
+
+    /// set the distance in bytes between two vertices.
+    ///
+    /// Defaults to the tightly packed size of the attributes.
+    pub fn stride(mut self, stride: u32) -> Self {
+        self.stride = Some(stride);
+        self
+    }
+
+    /// use indexed rendering. kind must be [`DataType::U8`], [`DataType::U16`] or [`DataType::U32`]
+    pub fn indices(mut self, indices: &'d [u8], kind: DataType) -> Self {
+        self.indices = Some((indices, kind));
+        self
+    }
+
+    /// usage hint for the buffers. Defaults to [`DrawMode::Static`]
+    pub fn draw_mode(mut self, draw_mode: DrawMode) -> Self {
+        self.draw_mode = draw_mode;
+        self
+    }
+
+    fn packed_stride(&self) -> u32 {
+        self.attributes
+            .iter()
+            .map(|a| a.size as u32 * a.kind.size() as u32)
+            .sum()
+    }
+
+    /// upload the data and create the vertex array object
+    pub fn build(self, gl: &GLContext) -> Mesh<'_> {
+        let stride = self.stride.unwrap_or_else(|| self.packed_stride());
+        let vao = gl.create_vertex_array();
+        gl.bind_vertex_array(&vao);
+        let vertex_buffer = gl.create_buffer();
+        gl.bind_buffer(BufferKind::Array, &vertex_buffer);
+        gl.buffer_data(BufferKind::Array, self.vertices, self.draw_mode);
+
+        let (index_buffer, count) = match self.indices {
+            Some((indices, kind)) => {
+                let buffer = gl.create_buffer();
+                gl.bind_buffer(BufferKind::ElementArray, &buffer);
+                gl.buffer_data(BufferKind::ElementArray, indices, self.draw_mode);
+                let count = indices.len() / kind.size();
+                (Some((buffer, kind)), count)
+            }
+            None => (None, self.vertices.len() / stride.max(1) as usize),
+        };
+
+        let mesh = Mesh {
+            gl,
+            vao,
+            vertex_buffer,
+            index_buffer,
+            attributes: self.attributes,
+            stride,
+            count,
+        };
+        mesh.setup_attributes();
+        gl.unbind_vertex_array(&mesh.vao);
+        mesh
+    }
+}
+
+/// A vertex array object owning its vertex and index buffers.
+///
+/// Like [`RenderTarget`](crate::RenderTarget), the mesh borrows the context and its OpenGL
+/// objects are deleted when it is dropped.
+pub struct Mesh<'a> {
+    gl: &'a GLContext,
+    vao: WebGLVertexArray,
+    vertex_buffer: WebGLBuffer,
+    index_buffer: Option<(WebGLBuffer, DataType)>,
+    attributes: Vec<VertexAttribute>,
+    stride: u32,
+    count: usize,
+}
+
+impl<'a> Mesh<'a> {
+    fn setup_attributes(&self) {
+        let gl = self.gl;
+        gl.bind_buffer(BufferKind::Array, &self.vertex_buffer);
+        for a in self.attributes.iter() {
+            gl.vertex_attrib_pointer(
+                a.location,
+                a.size,
+                a.kind,
+                a.normalized,
+                self.stride,
+                a.offset,
+            );
+            gl.enable_vertex_attrib_array(a.location);
+        }
+        if let Some((buffer, _)) = &self.index_buffer {
+            gl.bind_buffer(BufferKind::ElementArray, buffer);
+        }
+    }
+
+    /// number of vertices (or indices for an indexed mesh) submitted by [`Mesh::draw`]
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// render the whole mesh
+    pub fn draw(&self, mode: Primitives) {
+        let gl = self.gl;
+        gl.bind_vertex_array(&self.vao);
+        if !gl.is_webgl2 {
+            // no vertex array object on WebGL 1.0, the state has to be set again
+            self.setup_attributes();
+        }
+        match &self.index_buffer {
+            Some((_, kind)) => gl.draw_elements(mode, self.count, *kind, 0),
+            None => gl.draw_arrays(mode, self.count),
+        }
+        gl.unbind_vertex_array(&self.vao);
+    }
+}
+
+impl<'a> Drop for Mesh<'a> {
+    fn drop(&mut self) {
+        self.gl.delete_vertex_array(&self.vao);
+        self.gl.delete_buffer(&self.vertex_buffer);
+        if let Some((buffer, _)) = &self.index_buffer {
+            self.gl.delete_buffer(buffer);
+        }
+    }
+}
@@ -0,0 +1,150 @@
+//! Bookkeeping of the JavaScript objects handed out as integer references by the web backend.
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+
+/// objects indexed by the reference given to the user, starting at 1.
+///
+/// An object can be owned by another one, like the uniform locations of a program,
+/// and is then removed along with its owner.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ObjectTable<T> {
+    objects: RefCell<HashMap<i32, T>>,
+    owned: RefCell<HashMap<i32, Vec<i32>>>,
+    seq: Cell<i32>,
+}
+
+impl<T: Clone + PartialEq> ObjectTable<T> {
+    pub(crate) fn new() -> ObjectTable<T> {
+        ObjectTable {
+            objects: RefCell::new(HashMap::new()),
+            owned: RefCell::new(HashMap::new()),
+            seq: Cell::new(1),
+        }
+    }
+
+    /// store an object, returning its reference
+    pub(crate) fn add(&self, val: T) -> i32 {
+        let id = self.seq.get();
+        self.seq.set(id + 1);
+        self.objects.borrow_mut().insert(id, val);
+        id
+    }
+
+    /// store an object removed along with `owner`
+    pub(crate) fn add_owned(&self, owner: i32, val: T) -> i32 {
+        let id = self.add(val);
+        self.owned.borrow_mut().entry(owner).or_default().push(id);
+        id
+    }
+
+    pub(crate) fn get(&self, id: i32) -> Option<T> {
+        self.objects.borrow().get(&id).cloned()
+    }
+
+    /// the reference of an object already stored
+    pub(crate) fn find(&self, val: &T) -> Option<i32> {
+        self.objects
+            .borrow()
+            .iter()
+            .find(|(_, stored)| *stored == val)
+            .map(|(id, _)| *id)
+    }
+
+    /// the reference of an object, stored if it is not known yet
+    pub(crate) fn find_or_add(&self, val: T) -> i32 {
+        match self.find(&val) {
+            Some(id) => id,
+            None => self.add(val),
+        }
+    }
+
+    /// forget an object and the ones it owns, then pass it to `delete`.
+    /// Like OpenGL, an unknown reference is ignored
+    pub(crate) fn delete(&self, id: i32, delete: impl FnOnce(T)) {
+        if let Some(val) = self.take(id) {
+            delete(val);
+        }
+    }
+
+    /// remove an object and the ones it owns, returning it so that it can be deleted
+    fn take(&self, id: i32) -> Option<T> {
+        let owned = self.owned.borrow_mut().remove(&id).unwrap_or_default();
+        let mut objects = self.objects.borrow_mut();
+        for owned_id in owned {
+            objects.remove(&owned_id);
+        }
+        objects.remove(&id)
+    }
+
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> usize {
+        self.objects.borrow().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deleting_every_object_kind_empties_the_table() {
+        let table = ObjectTable::new();
+        let start = table.len();
+        let buffer = table.add("buffer");
+        let shader = table.add("shader");
+        let program = table.add("program");
+        table.add_owned(program, "uniform location");
+        table.add_owned(program, "uniform location");
+        let texture = table.add("texture");
+        let framebuffer = table.add("framebuffer");
+        let renderbuffer = table.add("renderbuffer");
+        let vertex_array = table.add("vertex array");
+        assert_eq!(table.len(), start + 9);
+
+        let mut deleted = Vec::new();
+        for id in [
+            buffer,
+            shader,
+            program,
+            texture,
+            framebuffer,
+            renderbuffer,
+            vertex_array,
+        ] {
+            table.delete(id, |val| deleted.push(val));
+        }
+        assert_eq!(deleted.len(), 7);
+        assert_eq!(table.len(), start);
+
+        // deleting twice is ignored
+        table.delete(buffer, |_| panic!("buffer deleted twice"));
+    }
+
+    #[test]
+    fn a_shader_deleted_while_attached_is_forgotten_once_detached() {
+        let table = ObjectTable::new();
+        let start = table.len();
+        let shader = table.add("shader");
+        // delete_shader while the shader is still attached to a program
+        table.delete(shader, drop);
+        // get_attached_shaders gives a new handle to the shader, detach_shader forgets it
+        let attached = table.find_or_add("shader");
+        assert_ne!(attached, shader);
+        assert_eq!(table.find_or_add("shader"), attached);
+        table.delete(attached, drop);
+        assert_eq!(table.len(), start);
+    }
+
+    #[test]
+    fn references_are_never_reused() {
+        let table = ObjectTable::new();
+        let first = table.add("texture");
+        table.delete(first, |val| assert_eq!(val, "texture"));
+        let second = table.add("texture");
+        assert_ne!(first, second);
+        assert_eq!(table.find(&"texture"), Some(second));
+        assert_eq!(table.get(first), None);
+        assert_eq!(table.get(second), Some("texture"));
+    }
+}
@@ -0,0 +1,58 @@
+//! Shader sources portable between OpenGL and WebGL.
+//!
+//! ```ignore
+//! let source = uni_gl::ShaderSourceBuilder::new(uni_gl::ShaderKind::Fragment, body).build(&gl);
+//! gl.shader_source(&shader, &source);
+//! ```
+
+use crate::glenum::*;
+use crate::GLContext;
+use crate::IS_GL_ES;
+
+/// Prepends the `#version` header and the default precision expected by the current context to a shader body.
+pub struct ShaderSourceBuilder<'s> {
+    kind: ShaderKind,
+    body: &'s str,
+    precision: &'s str,
+}
+
+impl<'s> ShaderSourceBuilder<'s> {
+    /// start from a shader body without `#version` line
+    pub fn new(kind: ShaderKind, body: &'s str) -> ShaderSourceBuilder<'s> {
+        ShaderSourceBuilder {
+            kind,
+            body,
+            precision: "mediump",
+        }
+    }
+
+    /// default float precision of fragment shaders on OpenGL ES / WebGL. Defaults to `mediump`
+    pub fn precision(mut self, precision: &'s str) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    /// the `#version` matching the context: `300 es` on WebGL 2.0, `100` on WebGL 1.0, `150` on native
+    pub fn version(gl: &GLContext) -> &'static str {
+        if !IS_GL_ES {
+            "150"
+        } else if gl.is_webgl2 {
+            "300 es"
+        } else {
+            "100"
+        }
+    }
+
+    /// return the complete source, ready for [`GLContext::shader_source`]
+    pub fn build(&self, gl: &GLContext) -> String {
+        let mut source = format!("#version {}\n", Self::version(gl));
+        // fragment shaders have no default float precision on ES
+        if IS_GL_ES {
+            if let ShaderKind::Fragment = self.kind {
+                source.push_str(&format!("precision {} float;\n", self.precision));
+            }
+        }
+        source.push_str(self.body);
+        source
+    }
+}
@@ -0,0 +1,1043 @@
+//! Methods common to all backends, built on top of the backend specific [`GLContext`] API.
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::borrow::Cow;
+use std::cell::{Cell, OnceCell, RefCell};
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::rc::Rc;
+
+use crate::common::{DrawCommand, LogLevel, Rect, WebGLProgram, WebGLShader, WebGLUniformLocation};
+use crate::glenum::*;
+use crate::image::Image;
+use crate::util::bytes_per_pixel;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::util::AsGlBytes;
+use crate::GLContext;
+
+/// number of values returned by glGetUniform for a uniform of the given type
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn uniform_components(utype: u32) -> usize {
+    match utype {
+        // vec2, ivec2, bvec2, uvec2
+        0x8B50 | 0x8B53 | 0x8B57 | 0x8DC6 => 2,
+        // vec3, ivec3, bvec3, uvec3
+        0x8B51 | 0x8B54 | 0x8B58 | 0x8DC7 => 3,
+        // vec4, ivec4, bvec4, uvec4, mat2
+        0x8B52 | 0x8B55 | 0x8B59 | 0x8DC8 | 0x8B5A => 4,
+        // mat2x3, mat3x2
+        0x8B65 | 0x8B67 => 6,
+        // mat2x4, mat4x2
+        0x8B66 | 0x8B69 => 8,
+        // mat3
+        0x8B5B => 9,
+        // mat3x4, mat4x3
+        0x8B68 | 0x8B6A => 12,
+        // mat4
+        0x8B5C => 16,
+        // scalars and samplers
+        _ => 1,
+    }
+}
+
+/// uniform locations looked up by name, per program
+pub(crate) type UniformLocations =
+    RefCell<HashMap<crate::webgl::Reference, HashMap<String, Option<WebGLUniformLocation>>>>;
+
+/// last values set through uni-gl, to skip redundant OpenGL calls.
+///
+/// Clones of a context drive the same OpenGL state, so they share the cache
+#[derive(Debug, Clone, Default)]
+pub(crate) struct StateCache(Rc<StateCacheValues>);
+
+#[derive(Debug, Default)]
+pub(crate) struct StateCacheValues {
+    /// enabled state of each capability
+    flags: RefCell<HashMap<i32, bool>>,
+    clear_color: Cell<Option<[f32; 4]>>,
+    /// compressed texture formats listed by the driver, queried once and kept by flush_state_cache
+    compressed_formats: OnceCell<Vec<u32>>,
+}
+
+impl Deref for StateCache {
+    type Target = StateCacheValues;
+    fn deref(&self) -> &StateCacheValues {
+        &self.0
+    }
+}
+
+impl PartialEq for StateCache {
+    fn eq(&self, other: &StateCache) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// in debug builds, panic if the byte offset of an indexed draw is not a multiple of the index size,
+/// which OpenGL reports as a bare invalid operation
+pub(crate) fn debug_check_index_offset(context: &str, kind: DataType, offset: u32) {
+    if cfg!(debug_assertions) {
+        assert!(
+            offset as usize % kind.size() == 0,
+            "{} : offset {} is not a multiple of the {:?} index size ({} bytes)",
+            context,
+            offset,
+            kind,
+            kind.size()
+        );
+    }
+}
+
+/// the pixel storage modes deciding where the rows of an image are in client memory
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct PixelLayout {
+    /// rows are padded to this number of bytes, except the last one
+    pub(crate) alignment: usize,
+    /// pixels in a row of the whole image, 0 for the width of the region
+    pub(crate) row_length: usize,
+    /// rows before the region
+    pub(crate) skip_rows: usize,
+    /// pixels before the region in each row
+    pub(crate) skip_pixels: usize,
+}
+
+/// number of bytes OpenGL reads or writes for an image of the given size
+#[cfg(not(target_arch = "wasm32"))]
+fn pixels_len(
+    width: u32,
+    height: u32,
+    format: PixelFormat,
+    kind: PixelType,
+    layout: PixelLayout,
+) -> usize {
+    if width == 0 || height == 0 {
+        return 0;
+    }
+    let pixel = bytes_per_pixel(format, kind);
+    let row = width as usize * pixel;
+    let row_length = if layout.row_length > 0 {
+        layout.row_length
+    } else {
+        width as usize
+    };
+    let alignment = layout.alignment.max(1);
+    let stride = (row_length * pixel).div_ceil(alignment) * alignment;
+    let skipped = layout.skip_rows * stride + layout.skip_pixels * pixel;
+    skipped + stride * (height as usize - 1) + row
+}
+
+/// panic if `len` bytes can't hold the pixels read or written by OpenGL
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn check_pixels_len(
+    context: &str,
+    len: usize,
+    width: u32,
+    height: u32,
+    format: PixelFormat,
+    kind: PixelType,
+    layout: PixelLayout,
+) {
+    let needed = pixels_len(width, height, format, kind, layout);
+    assert!(
+        len >= needed,
+        "{} : {}x{} {:?}/{:?} pixels need {} bytes but the buffer only holds {}",
+        context,
+        width,
+        height,
+        format,
+        kind,
+        needed,
+        len
+    );
+}
+
+/// whether an active uniform name returned by OpenGL designates the uniform name
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn same_uniform(active_name: &str, name: &str) -> bool {
+    // arrays are reported as name[0], each element has the type of the array
+    active_name.split('[').next() == name.split('[').next()
+}
+
+/// description of a glGetError code
+fn error_name(err: u32) -> &'static str {
+    match err {
+        0x0500 => "invalid enum",
+        0x0501 => "invalid value",
+        0x0502 => "invalid operation",
+        0x0503 => "stack overflow",
+        0x0504 => "stack underflow",
+        0x0505 => "out of memory",
+        0x0506 => "invalid framebuffer operation",
+        0x9242 => "context lost webgl",
+        _ => "unknown error",
+    }
+}
+
+impl GLContext {
+    /// set which messages uni-gl prints by itself. Defaults to [`LogLevel::Error`].
+    ///
+    /// Raising it to [`LogLevel::Info`] prints the context information, as the level
+    /// can only be set once the context is created
+    pub fn set_log_level(&self, level: LogLevel) {
+        let was_info = self.log_enabled(LogLevel::Info);
+        self.log_level.set(level);
+        if !was_info && self.log_enabled(LogLevel::Info) {
+            self.print_gl_info();
+        }
+    }
+
+    /// print the version and vendor of the context
+    pub(crate) fn print_gl_info(&self) {
+        let info = self.gpu_info();
+        GLContext::print(format!("opengl {}\n", info.version));
+        GLContext::print(format!(
+            "shading language {}\n",
+            info.shading_language_version
+        ));
+        GLContext::print(format!("vendor {}\n", info.vendor));
+    }
+
+    /// return the current [`LogLevel`]
+    pub fn log_level(&self) -> LogLevel {
+        self.log_level.get()
+    }
+
+    /// whether a message of the given level should be printed
+    pub(crate) fn log_enabled(&self, level: LogLevel) -> bool {
+        level != LogLevel::Off && level <= self.log_level.get()
+    }
+
+    /// print an internal message, filtered by the current log level
+    pub(crate) fn log_at<T: Into<String>>(&self, level: LogLevel, msg: T) {
+        if self.log_enabled(level) {
+            GLContext::print(format!("{}\n", msg.into()));
+        }
+    }
+
+    /// remove the null bytes that can't be passed to OpenGL as a C string, with a warning
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn strip_nul<'a>(&self, s: &'a str, context: &str) -> Cow<'a, str> {
+        if !s.contains('\0') {
+            return Cow::Borrowed(s);
+        }
+        self.log_at(
+            LogLevel::Error,
+            format!("WARNING {} : removed null bytes from the string", context),
+        );
+        Cow::Owned(s.replace('\0', ""))
+    }
+
+    /// record the state of a capability, returning false if it was already in this state
+    pub(crate) fn update_flag_cache(&self, flag: i32, enabled: bool) -> bool {
+        self.state_cache.flags.borrow_mut().insert(flag, enabled) != Some(enabled)
+    }
+
+    /// forget the cached state of a capability, after it was changed for a single draw buffer
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn forget_flag_cache(&self, flag: i32) {
+        self.state_cache.flags.borrow_mut().remove(&flag);
+    }
+
+    /// whether the compressed format is in [`GLContext::get_supported_compressed_formats`], queried once
+    pub(crate) fn is_compressed_format_listed(&self, compression: TextureCompression) -> bool {
+        self.state_cache
+            .compressed_formats
+            .get_or_init(|| self.get_supported_compressed_formats())
+            .contains(&(compression as u32))
+    }
+
+    /// record the clear color, returning false if it was already set
+    pub(crate) fn update_clear_color_cache(&self, color: [f32; 4]) -> bool {
+        self.state_cache.clear_color.replace(Some(color)) != Some(color)
+    }
+
+    /// forget the state cached by [`GLContext::enable`], [`GLContext::disable`] and [`GLContext::clear_color`].
+    ///
+    /// Call it when code outside of uni-gl may have changed them, so the next calls reach OpenGL
+    pub fn flush_state_cache(&self) {
+        self.state_cache.flags.borrow_mut().clear();
+        self.state_cache.clear_color.set(None);
+    }
+
+    fn set_flag(&self, flag: i32, enabled: bool) {
+        if enabled {
+            self.enable(flag);
+        } else {
+            self.disable(flag);
+        }
+    }
+
+    /// enable or disable depth testing ([`Flag::DepthTest`])
+    pub fn set_depth_test(&self, enabled: bool) {
+        self.set_flag(Flag::DepthTest as i32, enabled);
+    }
+
+    /// enable or disable blending ([`Flag::Blend`])
+    pub fn set_blend(&self, enabled: bool) {
+        self.set_flag(Flag::Blend as i32, enabled);
+    }
+
+    /// enable blending for colors with straight (non-premultiplied) alpha :
+    /// `src * src_alpha + dst * (1 - src_alpha)`
+    pub fn set_alpha_blend(&self) {
+        self.set_blend(true);
+        self.blend_func(BlendMode::SrcAlpha, BlendMode::OneMinusSrcAlpha);
+    }
+
+    /// enable blending for colors already multiplied by their alpha : `src + dst * (1 - src_alpha)`.
+    ///
+    /// Use it for premultiplied textures, straight alpha blending of them darkens the edges
+    pub fn set_premultiplied_alpha_blend(&self) {
+        self.set_blend(true);
+        self.blend_func(BlendMode::One, BlendMode::OneMinusSrcAlpha);
+    }
+
+    /// enable or disable face culling ([`Culling::CullFace`])
+    pub fn set_cull_face(&self, enabled: bool) {
+        self.set_flag(Culling::CullFace as i32, enabled);
+    }
+
+    /// enable or disable the scissor test ([`Flag::ScissorTest`])
+    pub fn set_scissor_test(&self, enabled: bool) {
+        self.set_flag(Flag::ScissorTest as i32, enabled);
+    }
+
+    /// reset the state commonly changed by rendering code to the OpenGL defaults :
+    /// depth func [`CompareFunc::Less`], blending, culling, scissor and stencil tests disabled,
+    /// all color components and depth writable, black clear color and texture unit 0 active.
+    ///
+    /// Useful as a known baseline, for example after third-party GL code ran.
+    pub fn reset_to_defaults(&self) {
+        self.flush_state_cache();
+        self.depth_func(CompareFunc::Less);
+        self.set_blend(false);
+        self.set_cull_face(false);
+        self.set_scissor_test(false);
+        self.set_flag(Flag::StencilTest as i32, false);
+        self.color_mask(true, true, true, true);
+        self.depth_mask(true);
+        self.clear_color(0.0, 0.0, 0.0, 0.0);
+        self.active_texture(0);
+    }
+
+    /// set the row alignment of the pixels returned by [`GLContext::read_pixels`] (1, 2, 4 or 8). Defaults to 4
+    pub fn set_pack_alignment(&self, alignment: i32) {
+        self.pixel_storei(PixelStorageMode::PackAlignment, alignment);
+    }
+
+    /// set the row alignment of the pixels passed to [`GLContext::tex_image2d`] (1, 2, 4 or 8). Defaults to 4
+    pub fn set_unpack_alignment(&self, alignment: i32) {
+        self.pixel_storei(PixelStorageMode::UnpackAlignment, alignment);
+    }
+
+    /// read a block of pixels from the current framebuffer into a tightly packed vector.
+    ///
+    /// The pack alignment is temporarily set to 1, so that RGB rows are not padded
+    pub fn read_pixels_to_vec(
+        &self,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+        format: PixelFormat,
+        kind: PixelType,
+    ) -> Vec<u8> {
+        let mut data = vec![0; width as usize * height as usize * bytes_per_pixel(format, kind)];
+        let alignment = self.get_pixel_storei(PixelStorageMode::PackAlignment);
+        self.set_pack_alignment(1);
+        self.read_pixels(x, y, width, height, format, kind, &mut data);
+        self.set_pack_alignment(alignment);
+        data
+    }
+
+    /// create a program from the sources of its vertex and fragment shaders.
+    ///
+    /// Returns the compilation or link log on failure. The shaders are deleted
+    /// once linked, and every object is deleted on failure
+    pub fn make_program(
+        &self,
+        vertex_src: &str,
+        fragment_src: &str,
+    ) -> Result<WebGLProgram, String> {
+        let vertex = self.make_shader(ShaderKind::Vertex, vertex_src)?;
+        let fragment = match self.make_shader(ShaderKind::Fragment, fragment_src) {
+            Ok(fragment) => fragment,
+            Err(log) => {
+                self.delete_shader(&vertex);
+                return Err(log);
+            }
+        };
+        let program = self.create_program();
+        self.attach_shader(&program, &vertex);
+        self.attach_shader(&program, &fragment);
+        let linked = self.try_link_program(&program);
+        // attached shaders are only flagged for deletion, they live as long as the program
+        self.delete_shader(&vertex);
+        self.delete_shader(&fragment);
+        match linked {
+            Ok(()) => Ok(program),
+            Err(log) => {
+                self.delete_program(&program);
+                Err(format!("program link failed: {}", log))
+            }
+        }
+    }
+
+    /// replace the shaders of a program and link it again, keeping the same handle.
+    ///
+    /// The new sources are compiled before touching the program, so it stays usable
+    /// if they don't compile. Its cached uniform locations are forgotten.
+    /// The previous shaders are only detached. Not available with the glow backend,
+    /// which can't list the attached shaders
+    #[cfg(not(all(not(target_arch = "wasm32"), feature = "glow-backend")))]
+    pub fn reload_program_sources(
+        &self,
+        program: &WebGLProgram,
+        vertex_src: &str,
+        fragment_src: &str,
+    ) -> Result<(), String> {
+        let vertex = self.make_shader(ShaderKind::Vertex, vertex_src)?;
+        let fragment = match self.make_shader(ShaderKind::Fragment, fragment_src) {
+            Ok(fragment) => fragment,
+            Err(log) => {
+                self.delete_shader(&vertex);
+                return Err(log);
+            }
+        };
+        for shader in self.get_attached_shaders(program) {
+            self.detach_shader(program, &shader);
+        }
+        self.attach_shader(program, &vertex);
+        self.attach_shader(program, &fragment);
+        let linked = self.try_link_program(program);
+        self.delete_shader(&vertex);
+        self.delete_shader(&fragment);
+        linked.map_err(|log| format!("program link failed: {}", log))
+    }
+
+    /// create and compile a shader, deleting it on failure
+    fn make_shader(&self, kind: ShaderKind, source: &str) -> Result<WebGLShader, String> {
+        let shader = self.create_shader(kind);
+        self.shader_source(&shader, source);
+        match self.try_compile_shader(&shader) {
+            Ok(()) => Ok(shader),
+            Err(log) => {
+                self.delete_shader(&shader);
+                Err(format!("{:?} shader compilation failed: {}", kind, log))
+            }
+        }
+    }
+
+    /// check that the attributes of a linked program have the expected locations,
+    /// for example those set with `layout(location = n)` in the shader.
+    ///
+    /// Returns a message listing every missing or misplaced attribute
+    pub fn verify_attrib_layout(
+        &self,
+        program: &WebGLProgram,
+        expected: &[(&str, u32)],
+    ) -> Result<(), String> {
+        let errors: Vec<String> = expected
+            .iter()
+            .filter_map(
+                |&(name, location)| match self.get_attrib_location(program, name) {
+                    Some(actual) if actual == location => None,
+                    Some(actual) => Some(format!(
+                        "attribute {} is at location {} instead of {}",
+                        name, actual, location
+                    )),
+                    None => Some(format!("attribute {} is not active", name)),
+                },
+            )
+            .collect();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join("\n"))
+        }
+    }
+
+    /// drain the OpenGL error queue and panic with every pending error, if any.
+    ///
+    /// Meant as an assertion point in tests, `context` tells which calls were checked
+    pub fn expect_no_gl_error(&self, context: &str) {
+        let mut errors = Vec::new();
+        loop {
+            let err = self.get_error();
+            if err == 0 {
+                break;
+            }
+            errors.push(format!("{} ({})", err, error_name(err)));
+        }
+        if !errors.is_empty() {
+            panic!("GLError: {} {}", context, errors.join(", "));
+        }
+    }
+
+    /// same as [`GLContext::tex_image2d_ext`] with 16 bits components,
+    /// for [`PixelType::UnsignedShort`], [`PixelType::HalfFloat`] or the packed 16 bits types
+    #[cfg(not(target_arch = "wasm32"))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn tex_image2d_u16(
+        &self,
+        target: TextureBindPoint,
+        level: u8,
+        internal_format: PixelFormat,
+        width: u16,
+        height: u16,
+        format: PixelFormat,
+        kind: PixelType,
+        pixels: &[u16],
+    ) {
+        self.tex_image2d_ext(
+            target,
+            level,
+            internal_format,
+            width,
+            height,
+            format,
+            kind,
+            pixels.as_gl_bytes(),
+        );
+    }
+
+    /// same as [`GLContext::tex_image2d_ext`] with [`PixelType::Float`] components
+    #[cfg(not(target_arch = "wasm32"))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn tex_image2d_f32(
+        &self,
+        target: TextureBindPoint,
+        level: u8,
+        internal_format: PixelFormat,
+        width: u16,
+        height: u16,
+        format: PixelFormat,
+        pixels: &[f32],
+    ) {
+        self.tex_image2d_ext(
+            target,
+            level,
+            internal_format,
+            width,
+            height,
+            format,
+            PixelType::Float,
+            pixels.as_gl_bytes(),
+        );
+    }
+
+    /// specify the image of a face of the bound cube map texture
+    #[allow(clippy::too_many_arguments)]
+    pub fn tex_image2d_cube_face(
+        &self,
+        face: CubeFace,
+        level: u8,
+        width: u16,
+        height: u16,
+        format: PixelFormat,
+        kind: PixelType,
+        pixels: &[u8],
+    ) {
+        self.tex_image2d(face.into(), level, width, height, format, kind, pixels);
+    }
+
+    /// set the viewport from a [`Rect`], see [`GLContext::viewport`]
+    pub fn viewport_rect(&self, rect: Rect) {
+        self.viewport(rect.x, rect.y, rect.w, rect.h);
+    }
+
+    /// set the scissor box from a [`Rect`], see [`GLContext::scissor`]
+    pub fn scissor_rect(&self, rect: Rect) {
+        self.scissor(rect.x, rect.y, rect.w, rect.h);
+    }
+
+    /// run `f` with the viewport set to `rect`. The previous viewport is restored afterward, even if `f` panics
+    pub fn with_viewport<F: FnOnce()>(&self, rect: Rect, f: F) {
+        let _guard = RectGuard {
+            gl: self,
+            previous: self.get_viewport(),
+            restore: GLContext::viewport_rect,
+        };
+        self.viewport_rect(rect);
+        f();
+    }
+
+    /// run `f` with the scissor box set to `rect`. The previous box is restored afterward, even if `f` panics.
+    ///
+    /// The scissor test itself must be enabled with [`GLContext::set_scissor_test`]
+    pub fn with_scissor<F: FnOnce()>(&self, rect: Rect, f: F) {
+        let _guard = RectGuard {
+            gl: self,
+            previous: self.get_scissor_box(),
+            restore: GLContext::scissor_rect,
+        };
+        self.scissor_rect(rect);
+        f();
+    }
+
+    /// draw into the color attachments 0 to `count - 1`, in that order
+    pub fn draw_buffers_n(&self, count: u32) {
+        const ATTACHMENTS: [ColorBuffer; 9] = [
+            ColorBuffer::ColorAttachment0,
+            ColorBuffer::ColorAttachment1,
+            ColorBuffer::ColorAttachment2,
+            ColorBuffer::ColorAttachment3,
+            ColorBuffer::ColorAttachment4,
+            ColorBuffer::ColorAttachment5,
+            ColorBuffer::ColorAttachment6,
+            ColorBuffer::ColorAttachment7,
+            ColorBuffer::ColorAttachment8,
+        ];
+        assert!(
+            count as usize <= ATTACHMENTS.len(),
+            "draw_buffers_n supports at most {} color attachments",
+            ATTACHMENTS.len()
+        );
+        self.draw_buffer(&ATTACHMENTS[..count as usize]);
+    }
+
+    /// submit a recorded draw call
+    pub fn draw(&self, cmd: DrawCommand) {
+        match cmd {
+            DrawCommand::Arrays { mode, count } => self.draw_arrays(mode, count),
+            DrawCommand::Elements {
+                mode,
+                count,
+                kind,
+                offset,
+            } => self.draw_elements(mode, count, kind, offset),
+            DrawCommand::ArraysInstanced {
+                mode,
+                count,
+                instances,
+            } => self.draw_arrays_instanced(mode, count, instances),
+            DrawCommand::ElementsInstanced {
+                mode,
+                count,
+                kind,
+                offset,
+                instances,
+            } => self.draw_elements_instanced(mode, count, kind, offset, instances),
+            DrawCommand::RangeElements {
+                mode,
+                start,
+                end,
+                count,
+                kind,
+                offset,
+            } => self.draw_range_elements(mode, start, end, count, kind, offset),
+        }
+    }
+
+    /// in debug builds, warn when a uniform is set while no program is in use
+    pub(crate) fn debug_check_program_in_use(&self, context: &str) {
+        if cfg!(debug_assertions) && self.log_enabled(LogLevel::Error) && !self.is_program_in_use()
+        {
+            self.log_at(
+                LogLevel::Error,
+                format!(
+                    "WARNING {} : no program in use, call use_program first",
+                    context
+                ),
+            );
+        }
+    }
+
+    /// the pname of an indexed parameter, logging an error for buffer kinds without indexed binding points
+    pub(crate) fn indexed_pname(&self, target: BufferKind, pname: IndexedParam) -> Option<u32> {
+        let name = pname.pname(target);
+        if name.is_none() {
+            self.log_at(
+                LogLevel::Error,
+                format!(
+                    "ERROR get_indexed_parameter_i32 : {:?} has no indexed binding points",
+                    target
+                ),
+            );
+        }
+        name
+    }
+
+    /// drop the cached uniform locations of a program, which change when it is linked again
+    pub(crate) fn forget_uniform_locations(&self, program: &WebGLProgram) {
+        self.uniform_locations.borrow_mut().remove(&**program);
+    }
+
+    /// call f with the location of the uniform called name.
+    /// Locations are cached per program, so only the first call queries OpenGL.
+    /// Nothing happens if the uniform does not exist
+    fn with_uniform_location<F: FnOnce(&WebGLUniformLocation)>(
+        &self,
+        program: &WebGLProgram,
+        name: &str,
+        f: F,
+    ) {
+        // the cache is not borrowed while calling OpenGL or f, which may use it again
+        let cached = self
+            .uniform_locations
+            .borrow()
+            .get(&**program)
+            .and_then(|locations| locations.get(name).cloned());
+        let location = match cached {
+            Some(location) => location,
+            None => {
+                let location = self.get_uniform_location(program, name);
+                self.uniform_locations
+                    .borrow_mut()
+                    .entry(**program)
+                    .or_default()
+                    .insert(name.to_string(), location.clone());
+                location
+            }
+        };
+        if let Some(location) = &location {
+            f(location);
+        }
+    }
+
+    /// same as [`GLContext::uniform_1i`], looking up the location by name.
+    ///
+    /// The locations are cached per program, so this is cheap enough to call every frame.
+    /// The program must be in use
+    pub fn set_uniform_1i_by_name(&self, program: &WebGLProgram, name: &str, value: i32) {
+        self.with_uniform_location(program, name, |l| self.uniform_1i(l, value));
+    }
+
+    /// same as [`GLContext::uniform_1f`], looking up the location by name. See [`GLContext::set_uniform_1i_by_name`]
+    pub fn set_uniform_1f_by_name(&self, program: &WebGLProgram, name: &str, value: f32) {
+        self.with_uniform_location(program, name, |l| self.uniform_1f(l, value));
+    }
+
+    /// same as [`GLContext::uniform_2f`], looking up the location by name. See [`GLContext::set_uniform_1i_by_name`]
+    pub fn set_uniform_2f_by_name(&self, program: &WebGLProgram, name: &str, value: (f32, f32)) {
+        self.with_uniform_location(program, name, |l| self.uniform_2f(l, value));
+    }
+
+    /// same as [`GLContext::uniform_3f`], looking up the location by name. See [`GLContext::set_uniform_1i_by_name`]
+    pub fn set_uniform_3f_by_name(
+        &self,
+        program: &WebGLProgram,
+        name: &str,
+        value: (f32, f32, f32),
+    ) {
+        self.with_uniform_location(program, name, |l| self.uniform_3f(l, value));
+    }
+
+    /// same as [`GLContext::uniform_4f`], looking up the location by name. See [`GLContext::set_uniform_1i_by_name`]
+    pub fn set_uniform_4f_by_name(
+        &self,
+        program: &WebGLProgram,
+        name: &str,
+        value: (f32, f32, f32, f32),
+    ) {
+        self.with_uniform_location(program, name, |l| self.uniform_4f(l, value));
+    }
+
+    /// same as [`GLContext::uniform_matrix_2fv`], looking up the location by name. See [`GLContext::set_uniform_1i_by_name`]
+    pub fn set_uniform_matrix_2fv_by_name(
+        &self,
+        program: &WebGLProgram,
+        name: &str,
+        value: &[[f32; 2]; 2],
+    ) {
+        self.with_uniform_location(program, name, |l| self.uniform_matrix_2fv(l, value));
+    }
+
+    /// same as [`GLContext::uniform_matrix_3fv`], looking up the location by name. See [`GLContext::set_uniform_1i_by_name`]
+    pub fn set_uniform_matrix_3fv_by_name(
+        &self,
+        program: &WebGLProgram,
+        name: &str,
+        value: &[[f32; 3]; 3],
+    ) {
+        self.with_uniform_location(program, name, |l| self.uniform_matrix_3fv(l, value));
+    }
+
+    /// same as [`GLContext::uniform_matrix_4fv`], looking up the location by name. See [`GLContext::set_uniform_1i_by_name`]
+    pub fn set_uniform_matrix_4fv_by_name(
+        &self,
+        program: &WebGLProgram,
+        name: &str,
+        value: &[[f32; 4]; 4],
+    ) {
+        self.with_uniform_location(program, name, |l| self.uniform_matrix_4fv(l, value));
+    }
+
+    /// set the minifying filter of the bound texture
+    pub fn set_min_filter(&self, kind: TextureKind, filter: TextureMinFilter) {
+        self.tex_parameteri(kind, TextureParameter::TextureMinFilter, filter as i32);
+    }
+
+    /// set the magnifying filter of the bound texture
+    pub fn set_mag_filter(&self, kind: TextureKind, filter: TextureMagFilter) {
+        self.tex_parameteri(kind, TextureParameter::TextureMagFilter, filter as i32);
+    }
+
+    /// set the wrap mode of the bound texture along the s (horizontal) coordinate
+    pub fn set_wrap_s(&self, kind: TextureKind, wrap: TextureWrap) {
+        self.tex_parameteri(kind, TextureParameter::TextureWrapS, wrap as i32);
+    }
+
+    /// set the wrap mode of the bound texture along the t (vertical) coordinate
+    pub fn set_wrap_t(&self, kind: TextureKind, wrap: TextureWrap) {
+        self.tex_parameteri(kind, TextureParameter::TextureWrapT, wrap as i32);
+    }
+
+    /// set the wrap mode of the bound texture along the r coordinate (WebGL 2.0)
+    pub fn set_wrap_r(&self, kind: TextureKind, wrap: TextureWrap) {
+        self.tex_parameteri(kind, TextureParameter::TextureWrapR, wrap as i32);
+    }
+
+    /// configure the bound depth texture to be sampled as a `sampler2DShadow`,
+    /// comparing the reference value with func (hardware PCF). WebGL 2.0 only
+    pub fn set_shadow_comparison(&self, kind: TextureKind, func: CompareFunc) {
+        self.tex_parameteri(
+            kind,
+            TextureParameter::TextureCompareMode,
+            TextureCompareMode::CompareRefToTexture as i32,
+        );
+        self.tex_parameteri(kind, TextureParameter::TextureCompareFunc, func as i32);
+    }
+
+    /// read a block of pixels from the current framebuffer into an [`Image`]
+    pub fn capture_framebuffer(
+        &self,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+        format: PixelFormat,
+        kind: PixelType,
+    ) -> Image {
+        Image {
+            width,
+            height,
+            format,
+            kind,
+            data: self.read_pixels_to_vec(x, y, width, height, format, kind),
+        }
+    }
+
+    /// clear only a rectangle of the current framebuffer.
+    ///
+    /// The scissor test is restored to its previous state, but the scissor box stays set to the rectangle.
+    pub fn clear_rect(&self, x: i32, y: i32, width: u32, height: u32, bits: BufferBit) {
+        let scissor_test = self.is_enabled(Flag::ScissorTest);
+        self.set_scissor_test(true);
+        self.scissor(x, y, width, height);
+        self.clear(bits);
+        self.set_scissor_test(scissor_test);
+    }
+
+    /// whether the last [`GLContext::compile_shader`] call on this shader succeeded
+    pub fn is_shader_compiled(&self, shader: &WebGLShader) -> bool {
+        self.get_shader_parameter(shader, ShaderParameter::CompileStatus) != 0
+    }
+
+    /// whether the shader was flagged for deletion
+    pub fn is_shader_deleted(&self, shader: &WebGLShader) -> bool {
+        self.get_shader_parameter(shader, ShaderParameter::DeleteStatus) != 0
+    }
+
+    /// the kind of a shader (vertex, fragment...), None if it is not a valid shader anymore
+    pub fn shader_type(&self, shader: &WebGLShader) -> Option<ShaderKind> {
+        let kind = self.get_shader_parameter(shader, ShaderParameter::ShaderType);
+        ShaderKind::try_from(kind as u32).ok()
+    }
+
+    /// whether the last [`GLContext::link_program`] call on this program succeeded
+    pub fn is_program_linked(&self, program: &WebGLProgram) -> bool {
+        self.get_program_parameter(program, ShaderParameter::LinkStatus) != 0
+    }
+
+    /// whether the program was flagged for deletion
+    pub fn is_program_deleted(&self, program: &WebGLProgram) -> bool {
+        self.get_program_parameter(program, ShaderParameter::DeleteStatus) != 0
+    }
+
+    /// number of active uniforms in a linked program
+    pub fn active_uniform_count(&self, program: &WebGLProgram) -> u32 {
+        self.get_program_parameter(program, ShaderParameter::ActiveUniforms) as u32
+    }
+
+    /// number of active attributes in a linked program
+    pub fn active_attribute_count(&self, program: &WebGLProgram) -> u32 {
+        self.get_program_parameter(program, ShaderParameter::ActiveAttributes) as u32
+    }
+
+    /// number of active uniform blocks in a linked program (WebGL 2.0)
+    pub fn active_uniform_block_count(&self, program: &WebGLProgram) -> u32 {
+        self.get_program_parameter(program, ShaderParameter::ActiveUniformBlocks) as u32
+    }
+
+    /// same as [`GLContext::vertex_attrib_pointer`], but the component type is deduced from T
+    /// and stride/offset are expressed in number of T elements instead of bytes.
+    ///
+    /// ```ignore
+    /// // interleaved position (3 floats) + uv (2 floats)
+    /// gl.vertex_attrib_pointer_typed::<f32>(position, AttributeSize::Three, false, 5, 0);
+    /// gl.vertex_attrib_pointer_typed::<f32>(uv, AttributeSize::Two, false, 5, 3);
+    /// ```
+    pub fn vertex_attrib_pointer_typed<T: GlPrimitive>(
+        &self,
+        location: u32,
+        size: AttributeSize,
+        normalized: bool,
+        stride_elems: u32,
+        offset_elems: u32,
+    ) {
+        let elem_size = T::SIZE as u32;
+        self.vertex_attrib_pointer(
+            location,
+            size,
+            T::GL_TYPE,
+            normalized,
+            stride_elems * elem_size,
+            offset_elems * elem_size,
+        );
+    }
+
+    /// same as [`GLContext::draw_elements`], but the index type is deduced from I (`u8`, `u16` or `u32`)
+    /// and the offset is expressed in number of indices instead of bytes.
+    ///
+    /// ```ignore
+    /// let indices: Vec<u16> = vec![0, 1, 2];
+    /// gl.buffer_data(BufferKind::ElementArray, &indices, DrawMode::Static);
+    /// gl.draw_elements_typed::<u16>(Primitives::Triangles, indices.len(), 0);
+    /// ```
+    pub fn draw_elements_typed<I: GlPrimitive>(
+        &self,
+        mode: Primitives,
+        indices_len: usize,
+        offset_elems: u32,
+    ) {
+        self.draw_elements(mode, indices_len, I::GL_TYPE, offset_elems * I::SIZE as u32);
+    }
+
+    /// make `unit` the active texture unit until the returned guard is dropped.
+    /// The previously active unit is then restored.
+    ///
+    /// ```ignore
+    /// {
+    ///     let _unit = gl.scoped_active_texture(3);
+    ///     gl.bind_texture(&texture);
+    /// } // the caller's active unit is back
+    /// ```
+    pub fn scoped_active_texture(&self, unit: u32) -> ActiveTextureGuard<'_> {
+        let previous = self.get_active_texture();
+        self.active_texture(unit);
+        ActiveTextureGuard { gl: self, previous }
+    }
+
+    /// make a sampler uniform read the texture bound to the given texture unit.
+    ///
+    /// Samplers are set with [`GLContext::uniform_1i`] and the unit index, not the texture.
+    /// The program must be in use
+    pub fn set_sampler(&self, location: &WebGLUniformLocation, unit: u32) {
+        self.uniform_1i(location, unit as i32);
+    }
+}
+
+/// generates setters skipping a missing uniform location, see [`GLContext::uniform_1f_opt`]
+macro_rules! uniform_opt {
+    ($($name:ident => $setter:ident($value:ty),)*) => {
+        impl GLContext {
+            $(
+                #[doc = concat!("like [`GLContext::", stringify!($setter), "`], doing nothing when the location is `None`")]
+                pub fn $name(&self, location: Option<&WebGLUniformLocation>, value: $value) {
+                    if let Some(location) = location {
+                        self.$setter(location, value);
+                    }
+                }
+            )*
+        }
+    };
+}
+
+uniform_opt! {
+    uniform_matrix_4fv_opt => uniform_matrix_4fv(&[[f32; 4]; 4]),
+    uniform_matrix_3fv_opt => uniform_matrix_3fv(&[[f32; 3]; 3]),
+    uniform_matrix_2fv_opt => uniform_matrix_2fv(&[[f32; 2]; 2]),
+    uniform_matrix_2x3fv_opt => uniform_matrix_2x3fv(&[f32]),
+    uniform_matrix_3x2fv_opt => uniform_matrix_3x2fv(&[f32]),
+    uniform_matrix_2x4fv_opt => uniform_matrix_2x4fv(&[f32]),
+    uniform_matrix_4x2fv_opt => uniform_matrix_4x2fv(&[f32]),
+    uniform_matrix_3x4fv_opt => uniform_matrix_3x4fv(&[f32]),
+    uniform_matrix_4x3fv_opt => uniform_matrix_4x3fv(&[f32]),
+    uniform_1i_opt => uniform_1i(i32),
+    uniform_1f_opt => uniform_1f(f32),
+    uniform_2f_opt => uniform_2f((f32, f32)),
+    uniform_3f_opt => uniform_3f((f32, f32, f32)),
+    uniform_4f_opt => uniform_4f((f32, f32, f32, f32)),
+    uniform_1ui_opt => uniform_1ui(u32),
+    uniform_2ui_opt => uniform_2ui((u32, u32)),
+    uniform_3ui_opt => uniform_3ui((u32, u32, u32)),
+    uniform_4ui_opt => uniform_4ui((u32, u32, u32, u32)),
+    uniform_1uiv_opt => uniform_1uiv(&[u32]),
+    uniform_2uiv_opt => uniform_2uiv(&[u32]),
+    uniform_3uiv_opt => uniform_3uiv(&[u32]),
+    uniform_4uiv_opt => uniform_4uiv(&[u32]),
+}
+
+/// restores a viewport or scissor box when dropped
+struct RectGuard<'a> {
+    gl: &'a GLContext,
+    previous: Rect,
+    restore: fn(&GLContext, Rect),
+}
+
+impl Drop for RectGuard<'_> {
+    fn drop(&mut self) {
+        (self.restore)(self.gl, self.previous);
+    }
+}
+
+/// Restores the previously active texture unit when dropped. See [`GLContext::scoped_active_texture`]
+#[must_use = "the previous texture unit is restored as soon as the guard is dropped"]
+pub struct ActiveTextureGuard<'a> {
+    gl: &'a GLContext,
+    previous: u32,
+}
+
+impl Drop for ActiveTextureGuard<'_> {
+    fn drop(&mut self) {
+        self.gl.active_texture(self.previous);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn needed(width: u32, height: u32, layout: PixelLayout) -> usize {
+        pixels_len(
+            width,
+            height,
+            PixelFormat::Rgb,
+            PixelType::UnsignedByte,
+            layout,
+        )
+    }
+
+    #[test]
+    fn pixels_len_pads_all_rows_but_the_last() {
+        let layout = PixelLayout {
+            alignment: 4,
+            ..PixelLayout::default()
+        };
+        // 5 RGB pixels are 15 bytes, padded to 16
+        assert_eq!(needed(5, 3, layout), 16 * 2 + 15);
+    }
+
+    #[test]
+    fn pixels_len_follows_unpack_row_length_and_skips() {
+        let layout = PixelLayout {
+            alignment: 1,
+            row_length: 10,
+            skip_rows: 2,
+            skip_pixels: 3,
+        };
+        // rows of the source image are 30 bytes long, the region starts at row 2, pixel 3
+        assert_eq!(needed(4, 2, layout), 2 * 30 + 3 * 3 + 30 + 4 * 3);
+    }
+}
@@ -0,0 +1,109 @@
+//! Batched upload of uniform values.
+//!
+//! ```ignore
+//! let mut uniforms = uni_gl::UniformBlock::new();
+//! uniforms
+//!     .set(&time_location, 1.5.into())
+//!     .set(&color_location, (1.0, 0.5, 0.0, 1.0).into());
+//! uniforms.apply(&gl);
+//! ```
+
+use std::collections::HashMap;
+
+use crate::common::*;
+use crate::webgl::Reference;
+use crate::GLContext;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// a value that can be assigned to a uniform
+pub enum UniformValue {
+    /// int, bool or sampler uniform
+    Int(i32),
+    /// float uniform
+    Float(f32),
+    /// vec2 uniform
+    Vec2((f32, f32)),
+    /// vec3 uniform
+    Vec3((f32, f32, f32)),
+    /// vec4 uniform
+    Vec4((f32, f32, f32, f32)),
+    /// mat2 uniform
+    Mat2([[f32; 2]; 2]),
+    /// mat3 uniform
+    Mat3([[f32; 3]; 3]),
+    /// mat4 uniform
+    Mat4([[f32; 4]; 4]),
+}
+
+macro_rules! impl_uniform_value_from {
+    ($($t:ty => $variant:ident),*) => {
+        $(impl From<$t> for UniformValue {
+            fn from(value: $t) -> Self {
+                UniformValue::$variant(value)
+            }
+        })*
+    };
+}
+
+impl_uniform_value_from!(
+    i32 => Int,
+    f32 => Float,
+    (f32, f32) => Vec2,
+    (f32, f32, f32) => Vec3,
+    (f32, f32, f32, f32) => Vec4,
+    [[f32; 2]; 2] => Mat2,
+    [[f32; 3]; 3] => Mat3,
+    [[f32; 4]; 4] => Mat4
+);
+
+/// A list of uniform values uploaded together with [`UniformBlock::apply`].
+#[derive(Debug, Default)]
+pub struct UniformBlock<'l> {
+    uniforms: Vec<(&'l WebGLUniformLocation, UniformValue)>,
+    /// position in uniforms of each location reference
+    indices: HashMap<Reference, usize>,
+}
+
+impl<'l> UniformBlock<'l> {
+    /// create an empty block
+    pub fn new() -> UniformBlock<'l> {
+        UniformBlock {
+            uniforms: Vec::new(),
+            indices: HashMap::new(),
+        }
+    }
+
+    /// set the value of a uniform. A value already set for this location is replaced
+    pub fn set(&mut self, location: &'l WebGLUniformLocation, value: UniformValue) -> &mut Self {
+        match self.indices.get(&location.reference) {
+            Some(&index) => self.uniforms[index].1 = value,
+            None => {
+                self.indices.insert(location.reference, self.uniforms.len());
+                self.uniforms.push((location, value));
+            }
+        }
+        self
+    }
+
+    /// remove all the values
+    pub fn clear(&mut self) {
+        self.uniforms.clear();
+        self.indices.clear();
+    }
+
+    /// upload all the values to the program currently in use
+    pub fn apply(&self, gl: &GLContext) {
+        for (location, value) in self.uniforms.iter() {
+            match *value {
+                UniformValue::Int(v) => gl.uniform_1i(location, v),
+                UniformValue::Float(v) => gl.uniform_1f(location, v),
+                UniformValue::Vec2(v) => gl.uniform_2f(location, v),
+                UniformValue::Vec3(v) => gl.uniform_3f(location, v),
+                UniformValue::Vec4(v) => gl.uniform_4f(location, v),
+                UniformValue::Mat2(ref v) => gl.uniform_matrix_2fv(location, v),
+                UniformValue::Mat3(ref v) => gl.uniform_matrix_3fv(location, v),
+                UniformValue::Mat4(ref v) => gl.uniform_matrix_4fv(location, v),
+            }
+        }
+    }
+}
@@ -0,0 +1,164 @@
+//! Conversion of Rust data into the raw bytes expected by OpenGL.
+//!
+//! ```ignore
+//! let vertices: Vec<f32> = vec![-0.7, -0.7, 0.0, 0.7, -0.7, 0.0, 0.0, 0.7, 0.0];
+//! gl.buffer_data(uni_gl::BufferKind::Array, &vertices, uni_gl::DrawMode::Static);
+//! ```
+
+use std::mem::size_of_val;
+use std::slice;
+
+use crate::glenum::{PixelFormat, PixelType};
+
+/// Plain data types that can be viewed as bytes.
+///
+/// # Safety
+///
+/// The type must have no padding bytes, and any bit pattern must be a valid value.
+/// This is the case for the numeric types and `#[repr(C)]` structs made only of them without padding.
+pub unsafe trait Pod: Copy {}
+
+unsafe impl Pod for i8 {}
+unsafe impl Pod for u8 {}
+unsafe impl Pod for i16 {}
+unsafe impl Pod for u16 {}
+unsafe impl Pod for i32 {}
+unsafe impl Pod for u32 {}
+unsafe impl Pod for i64 {}
+unsafe impl Pod for u64 {}
+unsafe impl Pod for f32 {}
+unsafe impl Pod for f64 {}
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
+
+/// Data that can be uploaded to OpenGL, see [`GLContext::buffer_data`](crate::GLContext::buffer_data)
+pub trait AsGlBytes {
+    /// the data as raw bytes, in native endianness
+    fn as_gl_bytes(&self) -> &[u8];
+}
+
+impl<T: Pod> AsGlBytes for [T] {
+    fn as_gl_bytes(&self) -> &[u8] {
+        // Pod guarantees there is no padding or invalid byte in T
+        unsafe { slice::from_raw_parts(self.as_ptr() as *const u8, size_of_val(self)) }
+    }
+}
+
+impl<T: Pod, const N: usize> AsGlBytes for [T; N] {
+    fn as_gl_bytes(&self) -> &[u8] {
+        self[..].as_gl_bytes()
+    }
+}
+
+impl<T: Pod> AsGlBytes for Vec<T> {
+    fn as_gl_bytes(&self) -> &[u8] {
+        self[..].as_gl_bytes()
+    }
+}
+
+impl<B: AsGlBytes + ?Sized> AsGlBytes for &B {
+    fn as_gl_bytes(&self) -> &[u8] {
+        (**self).as_gl_bytes()
+    }
+}
+
+/// number of bytes used by a pixel of the given client format and type,
+/// to size the buffers given to `read_pixels` or `tex_image2d`.
+///
+/// ```ignore
+/// // 3 bytes per pixel, rows padded to 4 bytes unless the pack alignment is 1
+/// let len = width * height * uni_gl::bytes_per_pixel(uni_gl::PixelFormat::Rgb, uni_gl::PixelType::UnsignedByte);
+/// ```
+pub fn bytes_per_pixel(format: PixelFormat, kind: PixelType) -> usize {
+    let components = match format {
+        PixelFormat::DepthComponent
+        | PixelFormat::DepthComponent16
+        | PixelFormat::DepthComponent24
+        | PixelFormat::DepthComponent32f
+        | PixelFormat::Alpha
+        | PixelFormat::Luminance
+        | PixelFormat::Red
+        | PixelFormat::R8
+        | PixelFormat::R16f
+        | PixelFormat::R32f => 1,
+        PixelFormat::LuminanceAlpha
+        | PixelFormat::Rg
+        | PixelFormat::Rg8
+        | PixelFormat::Rg16f
+        | PixelFormat::Rg32f
+        | PixelFormat::DepthStencil
+        | PixelFormat::Depth24Stencil8 => 2,
+        PixelFormat::Rgb | PixelFormat::Rgb8 => 3,
+        PixelFormat::Rgba
+        | PixelFormat::Rgba8
+        | PixelFormat::Srgb8Alpha8
+        | PixelFormat::Rgba16f
+        | PixelFormat::Rgba32f => 4,
+    };
+    match kind {
+        // packed types hold the whole pixel
+        PixelType::UnsignedShort4444
+        | PixelType::UnsignedShort5551
+        | PixelType::UnsignedShort565 => 2,
+        PixelType::UnsignedInt248 => 4,
+        PixelType::UnsignedByte => components,
+        PixelType::UnsignedShort | PixelType::HalfFloat => components * 2,
+        PixelType::UnsignedInt | PixelType::Float => components * 4,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn f32_slice_bytes() {
+        let values = [1.0f32, -2.5];
+        let bytes = values[..].as_gl_bytes();
+        assert_eq!(bytes.len(), 8);
+        assert_eq!(&bytes[..4], &1.0f32.to_ne_bytes());
+        assert_eq!(&bytes[4..], &(-2.5f32).to_ne_bytes());
+    }
+
+    #[test]
+    fn u16_vec_bytes() {
+        let indices: Vec<u16> = vec![0, 1, 0x0203];
+        let bytes = indices.as_gl_bytes();
+        assert_eq!(bytes.len(), 6);
+        assert_eq!(&bytes[4..], &0x0203u16.to_ne_bytes());
+    }
+
+    #[test]
+    fn nested_arrays_are_contiguous() {
+        let positions = [[0.0f32, 1.0, 2.0], [3.0, 4.0, 5.0]];
+        let bytes = positions.as_gl_bytes();
+        assert_eq!(bytes.len(), 24);
+        let expected: Vec<u8> = (0..6).flat_map(|i| (i as f32).to_ne_bytes()).collect();
+        assert_eq!(bytes, &expected[..]);
+    }
+
+    #[test]
+    fn reference_bytes_match_the_referenced_data() {
+        let values = vec![7u32, 8];
+        let reference = &values;
+        assert_eq!(reference.as_gl_bytes(), values.as_gl_bytes());
+    }
+
+    #[test]
+    fn empty_slice_has_no_bytes() {
+        let empty: &[f32] = &[];
+        assert_eq!(empty.as_gl_bytes(), &[] as &[u8]);
+    }
+
+    #[test]
+    fn rgb_bytes_per_pixel() {
+        assert_eq!(
+            bytes_per_pixel(PixelFormat::Rgb, PixelType::UnsignedByte),
+            3
+        );
+        assert_eq!(bytes_per_pixel(PixelFormat::Rgba, PixelType::Float), 16);
+        assert_eq!(
+            bytes_per_pixel(PixelFormat::Rgba, PixelType::UnsignedShort4444),
+            2
+        );
+    }
+}
@@ -1,16 +1,24 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
+use std::rc::Rc;
 
-use js_sys::{Array, Object, Reflect};
+use js_sys::{Array, Float32Array, Int32Array, Object, Reflect, Uint16Array};
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
 use web_sys::HtmlCanvasElement;
 
 use crate::common::*;
 use crate::glenum::*;
+use crate::objects::ObjectTable;
+use crate::shared::{debug_check_index_offset, StateCache, UniformLocations};
+use crate::util::AsGlBytes;
 
 pub type Reference = i32;
 
+// WEBGL_debug_renderer_info parameters
+const UNMASKED_VENDOR_WEBGL: u32 = 0x9245;
+const UNMASKED_RENDERER_WEBGL: u32 = 0x9246;
+
 macro_rules! gl_call {
     ($gl:expr, $func:ident, $($params:expr),*) => {{
         match $gl {
@@ -36,18 +44,50 @@ pub enum WebContext {
 pub struct GLContext {
     pub gl: WebContext,
     pub is_webgl2: bool,
-    dict: RefCell<HashMap<i32, JsValue>>,
-    seq: RefCell<i32>,
+    objects: ObjectTable<JsValue>,
+    pub(crate) log_level: Cell<LogLevel>,
+    pub(crate) uniform_locations: UniformLocations,
+    pub(crate) state_cache: StateCache,
+    // WebGL 1.0 has no vertex array object to hold the index buffer binding,
+    // so it is recorded per vertex array id, 0 being no vertex array
+    bound_vertex_array: Cell<i32>,
+    element_buffers: RefCell<HashMap<i32, i32>>,
+    // shaders of a kind WebGL doesn't have, with the error reported when compiling them
+    unsupported_shaders: RefCell<HashMap<i32, String>>,
 }
 
 pub type WebGLContext<'a> = &'a HtmlCanvasElement;
 
+/// the attributes actually granted by the browser when the context was created
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ContextAttributes {
+    pub alpha: bool,
+    pub depth: bool,
+    pub stencil: bool,
+    pub antialias: bool,
+    pub premultiplied_alpha: bool,
+    pub preserve_drawing_buffer: bool,
+}
+
 impl WebGLRenderingContext {
     pub fn new(canvas: WebGLContext) -> WebGLRenderingContext {
         WebGLRenderingContext {
             common: GLContext::new(&canvas.clone().into()),
         }
     }
+
+    /// create a context that several owners can keep, see [`WebGLRenderingContext::new`].
+    ///
+    /// The JavaScript objects are stored in `RefCell`s, which is sound because
+    /// WebGL contexts are only used from the thread that created them.
+    pub fn new_shared(canvas: WebGLContext) -> Rc<WebGLRenderingContext> {
+        Rc::new(WebGLRenderingContext::new(canvas))
+    }
+
+    pub fn new_with_size(canvas: WebGLContext, _width: u32, _height: u32) -> WebGLRenderingContext {
+        // the viewport is always set from the drawing buffer of the canvas
+        WebGLRenderingContext::new(canvas)
+    }
 }
 
 impl GLContext {
@@ -64,16 +104,24 @@ impl GLContext {
 
     // utilities to store and retrieve js objects as u32
     fn add(&self, val: JsValue) -> i32 {
-        let id = *self.seq.borrow();
-        *self.seq.borrow_mut() = id + 1;
-        self.dict.borrow_mut().insert(id, val);
-        id
+        self.objects.add(val)
     }
     fn get(&self, id: i32) -> Option<JsValue> {
-        self.dict.borrow().get(&id).map(|o| o.clone())
+        self.objects.get(id)
+    }
+    // deleting an object must go through here, so that it is forgotten
+    fn delete_object<O: JsCast>(&self, id: i32, delete: impl FnOnce(&O)) {
+        self.objects.delete(id, |val| delete(&val.unchecked_into()));
     }
-    fn remove(&self, id: i32) {
-        self.dict.borrow_mut().remove(&id);
+
+    // the viewport covers the drawing buffer, whatever its size when the context is created
+    fn set_initial_viewport(&self) {
+        self.viewport(
+            0,
+            0,
+            self.drawing_buffer_width(),
+            self.drawing_buffer_height(),
+        );
     }
 
     pub fn new<'a>(canvas: &HtmlCanvasElement) -> GLContext {
@@ -94,10 +142,16 @@ impl GLContext {
             let context = GLContext {
                 gl: WebContext::Gl2(gl),
                 is_webgl2: true,
-                dict: RefCell::new(HashMap::new()),
-                seq: RefCell::new(1),
+                objects: ObjectTable::new(),
+                log_level: Cell::new(LogLevel::default()),
+                uniform_locations: RefCell::new(HashMap::new()),
+                state_cache: StateCache::default(),
+                bound_vertex_array: Cell::new(0),
+                element_buffers: RefCell::new(HashMap::new()),
+                unsupported_shaders: RefCell::new(HashMap::new()),
             };
             context.display_gl_info();
+            context.set_initial_viewport();
             return context;
         }
         if let Ok(gl) = canvas
@@ -109,10 +163,16 @@ impl GLContext {
             let context = GLContext {
                 gl: WebContext::Gl(gl),
                 is_webgl2: false,
-                dict: RefCell::new(HashMap::new()),
-                seq: RefCell::new(1),
+                objects: ObjectTable::new(),
+                log_level: Cell::new(LogLevel::default()),
+                uniform_locations: RefCell::new(HashMap::new()),
+                state_cache: StateCache::default(),
+                bound_vertex_array: Cell::new(0),
+                element_buffers: RefCell::new(HashMap::new()),
+                unsupported_shaders: RefCell::new(HashMap::new()),
             };
             context.display_gl_info();
+            context.set_initial_viewport();
             return context;
         }
         panic!("No webgl context found");
@@ -125,6 +185,42 @@ impl GLContext {
             .unwrap()
     }
 
+    fn get_parameter_i32(&self, id: u32) -> i32 {
+        gl_call!(&self.gl, get_parameter, id)
+            .unwrap()
+            .as_f64()
+            .unwrap() as i32
+    }
+
+    fn get_parameter_range(&self, id: u32) -> (f32, f32) {
+        let range = Float32Array::from(gl_call!(&self.gl, get_parameter, id).unwrap());
+        (range.get_index(0), range.get_index(1))
+    }
+
+    fn get_parameter_rect(&self, id: u32) -> Rect {
+        let rect = Int32Array::from(gl_call!(&self.gl, get_parameter, id).unwrap());
+        Rect {
+            x: rect.get_index(0),
+            y: rect.get_index(1),
+            w: rect.get_index(2) as u32,
+            h: rect.get_index(3) as u32,
+        }
+    }
+
+    // the WebGL 2.0 context, or None with an error message on WebGL 1.0
+    fn webgl2(&self, name: &str) -> Option<&web_sys::WebGl2RenderingContext> {
+        match &self.gl {
+            WebContext::Gl2(gl) => Some(gl),
+            WebContext::Gl(_) => {
+                self.log_at(
+                    LogLevel::Error,
+                    format!("ERROR {} requires WebGL 2.0", name),
+                );
+                None
+            }
+        }
+    }
+
     fn get_extension(&self, ext_name: &str) -> bool {
         gl_call!(&self.gl, get_extension, ext_name)
             .unwrap()
@@ -133,22 +229,91 @@ impl GLContext {
 
     fn display_gl_info(&self) {
         self.get_extension("WEBGL_depth_texture");
-        print(&format!(
-            "opengl {}",
-            self.get_parameter(web_sys::WebGl2RenderingContext::VERSION)
-        ));
-        print(&format!(
-            "shading language {}",
-            self.get_parameter(web_sys::WebGl2RenderingContext::SHADING_LANGUAGE_VERSION)
-        ));
-        print(&format!(
-            "vendor {}",
-            self.get_parameter(web_sys::WebGl2RenderingContext::VENDOR)
-        ));
+        if self.log_enabled(LogLevel::Info) {
+            self.print_gl_info();
+        }
+    }
+
+    pub fn gl_version(&self) -> (u32, u32) {
+        // the WebGL version rather than the underlying OpenGL one
+        if self.is_webgl2 {
+            (2, 0)
+        } else {
+            (1, 0)
+        }
+    }
+
+    pub fn default_vertex_array(&self) -> Option<&WebGLVertexArray> {
+        // WebGL's implicit vertex array is not an object
+        None
+    }
+
+    pub fn is_core_profile(&self) -> bool {
+        // WebGL has no profiles
+        false
+    }
+
+    pub fn supports(&self, feature: Feature) -> bool {
+        // querying an extension also enables it
+        match feature {
+            Feature::FloatTextures => self.is_webgl2 || self.get_extension("OES_texture_float"),
+            Feature::ColorBufferFloat if self.is_webgl2 => {
+                self.get_extension("EXT_color_buffer_float")
+            }
+            Feature::ColorBufferFloat => self.get_extension("WEBGL_color_buffer_float"),
+            Feature::DepthTextures => self.is_webgl2 || self.get_extension("WEBGL_depth_texture"),
+            // the WebGL 1.0 extensions for these are not wired into uni-gl
+            Feature::VertexArrayObjects
+            | Feature::InstancedArrays
+            | Feature::MultipleRenderTargets
+            | Feature::UniformBufferObjects
+            | Feature::TransformFeedback => self.is_webgl2,
+        }
+    }
+
+    pub fn get_viewport(&self) -> Rect {
+        self.get_parameter_rect(web_sys::WebGl2RenderingContext::VIEWPORT)
+    }
+
+    pub fn get_scissor_box(&self) -> Rect {
+        self.get_parameter_rect(web_sys::WebGl2RenderingContext::SCISSOR_BOX)
+    }
+
+    pub fn line_width_range(&self) -> (f32, f32) {
+        self.get_parameter_range(web_sys::WebGl2RenderingContext::ALIASED_LINE_WIDTH_RANGE)
+    }
+
+    pub fn point_size_range(&self) -> (f32, f32) {
+        self.get_parameter_range(web_sys::WebGl2RenderingContext::ALIASED_POINT_SIZE_RANGE)
+    }
+
+    pub fn line_width(&self, width: f32) {
+        gl_call!(&self.gl, line_width, width);
+    }
+
+    pub fn gpu_info(&self) -> GpuInfo {
+        // the real vendor and renderer are hidden behind WEBGL_debug_renderer_info
+        let (vendor, renderer) = if self.get_extension("WEBGL_debug_renderer_info") {
+            (UNMASKED_VENDOR_WEBGL, UNMASKED_RENDERER_WEBGL)
+        } else {
+            (
+                web_sys::WebGl2RenderingContext::VENDOR,
+                web_sys::WebGl2RenderingContext::RENDERER,
+            )
+        };
+        GpuInfo {
+            vendor: self.get_parameter(vendor),
+            renderer: self.get_parameter(renderer),
+            version: self.get_parameter(web_sys::WebGl2RenderingContext::VERSION),
+            shading_language_version: self
+                .get_parameter(web_sys::WebGl2RenderingContext::SHADING_LANGUAGE_VERSION),
+        }
     }
 
     pub fn clear_color(&self, r: f32, g: f32, b: f32, a: f32) {
-        gl_call!(&self.gl, clear_color, r, g, b, a);
+        if self.update_clear_color_cache([r, g, b, a]) {
+            gl_call!(&self.gl, clear_color, r, g, b, a);
+        }
     }
 
     pub fn clear(&self, bit: BufferBit) {
@@ -156,6 +321,16 @@ impl GLContext {
     }
 
     pub fn compile_shader(&self, shader: &WebGLShader) {
+        if let Err(log) = self.try_compile_shader(shader) {
+            self.log_at(LogLevel::Error, "Error in shader compilation :");
+            self.log_at(LogLevel::Error, log);
+        }
+    }
+
+    pub(crate) fn try_compile_shader(&self, shader: &WebGLShader) -> Result<(), String> {
+        if let Some(msg) = self.unsupported_shaders.borrow().get(&shader.0) {
+            return Err(msg.clone());
+        }
         let shader: web_sys::WebGlShader = self.get(shader.0).unwrap().into();
         gl_call!(&self.gl, compile_shader, &shader);
         let compiled = gl_call!(
@@ -165,12 +340,16 @@ impl GLContext {
             web_sys::WebGl2RenderingContext::COMPILE_STATUS
         );
         if !compiled {
-            print("Error in shader compilation :");
-            print(&format!(
-                "{}",
-                gl_call!(&self.gl, get_shader_info_log, &shader).unwrap(),
-            ));
+            return Err(gl_call!(&self.gl, get_shader_info_log, &shader).unwrap());
         }
+        Ok(())
+    }
+
+    pub fn delete_shader(&self, shader: &WebGLShader) {
+        self.unsupported_shaders.borrow_mut().remove(&shader.0);
+        self.delete_object(shader.0, |shader: &web_sys::WebGlShader| {
+            gl_call!(&self.gl, delete_shader, Some(shader))
+        });
     }
 
     pub fn use_program(&self, program: &WebGLProgram) {
@@ -178,6 +357,16 @@ impl GLContext {
         gl_call!(&self.gl, use_program, Some(&program));
     }
 
+    pub(crate) fn is_program_in_use(&self) -> bool {
+        !gl_call!(
+            &self.gl,
+            get_parameter,
+            web_sys::WebGl2RenderingContext::CURRENT_PROGRAM
+        )
+        .unwrap()
+        .is_null()
+    }
+
     pub fn get_attrib_location(&self, program: &WebGLProgram, name: &str) -> Option<u32> {
         let program: web_sys::WebGlProgram = self.get(program.0).unwrap().into();
         let loc = gl_call!(&self.gl, get_attrib_location, &program, name);
@@ -199,13 +388,133 @@ impl GLContext {
         gl_call!(&self.gl, bind_buffer, kind as u32, Some(&buffer));
     }
 
-    pub fn buffer_data(&self, kind: BufferKind, data: &[u8], draw: DrawMode) {
+    pub fn bind_buffer_base(&self, kind: BufferKind, index: u32, buffer: &WebGLBuffer) {
+        if let Some(gl) = self.webgl2("bind_buffer_base") {
+            let buffer: web_sys::WebGlBuffer = self.get(buffer.0).unwrap().into();
+            gl.bind_buffer_base(kind as u32, index, Some(&buffer));
+        }
+    }
+
+    pub fn bind_buffer_range(
+        &self,
+        kind: BufferKind,
+        index: u32,
+        buffer: &WebGLBuffer,
+        offset: usize,
+        size: usize,
+    ) {
+        if let Some(gl) = self.webgl2("bind_buffer_range") {
+            let buffer: web_sys::WebGlBuffer = self.get(buffer.0).unwrap().into();
+            gl.bind_buffer_range_with_i32_and_i32(
+                kind as u32,
+                index,
+                Some(&buffer),
+                offset as i32,
+                size as i32,
+            );
+        }
+    }
+
+    pub fn get_indexed_parameter_i32(
+        &self,
+        target: BufferKind,
+        index: u32,
+        pname: IndexedParam,
+    ) -> i32 {
+        let gl = match self.webgl2("get_indexed_parameter_i32") {
+            Some(gl) => gl,
+            None => return 0,
+        };
+        let name = match self.indexed_pname(target, pname) {
+            Some(name) => name,
+            None => return 0,
+        };
+        let value = match gl.get_indexed_parameter(name, index) {
+            Ok(value) => value,
+            Err(_) => return 0,
+        };
+        match pname {
+            // map the bound buffer back to its handle
+            IndexedParam::Binding => self.objects.find(&value).unwrap_or(0),
+            _ => value.as_f64().unwrap_or(0.0) as i32,
+        }
+    }
+
+    // read and copy usage hints only exist on webgl 2
+    fn buffer_usage(&self, draw: DrawMode) -> u32 {
+        if self.is_webgl2 {
+            return draw as u32;
+        }
+        match draw {
+            DrawMode::StaticRead | DrawMode::StaticCopy => DrawMode::Static as u32,
+            DrawMode::DynamicRead | DrawMode::DynamicCopy => DrawMode::Dynamic as u32,
+            DrawMode::StreamRead | DrawMode::StreamCopy => DrawMode::Stream as u32,
+            _ => draw as u32,
+        }
+    }
+
+    pub fn buffer_data(&self, kind: BufferKind, data: impl AsGlBytes, draw: DrawMode) {
+        let data = data.as_gl_bytes();
         gl_call!(
             &self.gl,
             buffer_data_with_u8_array,
             kind as u32,
             data,
-            draw as u32
+            self.buffer_usage(draw)
+        );
+    }
+
+    pub fn buffer_sub_data(&self, kind: BufferKind, offset: u32, data: impl AsGlBytes) {
+        gl_call!(
+            &self.gl,
+            buffer_sub_data_with_i32_and_u8_array,
+            kind as u32,
+            offset as i32,
+            data.as_gl_bytes()
+        );
+    }
+
+    pub fn buffer_data_size(&self, kind: BufferKind, size: usize, draw: DrawMode) {
+        gl_call!(
+            &self.gl,
+            buffer_data_with_f64,
+            kind as u32,
+            size as f64,
+            self.buffer_usage(draw)
+        );
+    }
+
+    pub fn get_buffer_parameter_i32(&self, kind: BufferKind, pname: BufferParameter) -> i32 {
+        let val = gl_call!(&self.gl, get_buffer_parameter, kind as u32, pname as u32);
+        val.as_f64().unwrap() as i32
+    }
+
+    pub fn buffer_storage(&self, _kind: BufferKind, _size: usize, _flags: u32) {
+        // immutable buffer storage does not exist in WebGL
+        self.log_at(
+            LogLevel::Error,
+            "ERROR buffer_storage is not supported on WebGL",
+        );
+    }
+
+    pub fn map_buffer_range(
+        &self,
+        _kind: BufferKind,
+        _offset: usize,
+        _length: usize,
+        _access: u32,
+    ) -> *mut u8 {
+        self.log_at(
+            LogLevel::Error,
+            "ERROR map_buffer_range is not supported on WebGL",
+        );
+        std::ptr::null_mut()
+    }
+
+    pub fn unmap_buffer(&self, _kind: BufferKind) {
+        self.log_at(
+            LogLevel::Error,
+            "ERROR unmap_buffer is not supported on WebGL",
         );
     }
 
@@ -218,10 +527,35 @@ impl GLContext {
     }
 
     pub fn bind_vertex_array(&self, vao: &WebGLVertexArray) {
-        let vao: web_sys::WebGlVertexArrayObject = self.get(vao.0).unwrap().into();
+        let id = vao.0;
+        let vao: web_sys::WebGlVertexArrayObject = self.get(id).unwrap().into();
         match &self.gl {
             WebContext::Gl2(gl) => gl.bind_vertex_array(Some(&vao)),
-            WebContext::Gl(_) => (), // not supported on webgl
+            WebContext::Gl(_) => self.restore_element_buffer(id), // only the index buffer is emulated on webgl
+        }
+    }
+
+    pub fn bind_element_buffer(&self, buffer: &WebGLBuffer) {
+        if let WebContext::Gl(_) = &self.gl {
+            self.element_buffers
+                .borrow_mut()
+                .insert(self.bound_vertex_array.get(), buffer.0);
+        }
+        self.bind_buffer(BufferKind::ElementArray, buffer);
+    }
+
+    /// emulate the index buffer binding of vertex arrays on WebGL 1.0
+    fn restore_element_buffer(&self, vao: i32) {
+        self.bound_vertex_array.set(vao);
+        let buffer = self.element_buffers.borrow().get(&vao).copied();
+        if let Some(buffer) = buffer.and_then(|id| self.get(id)) {
+            let buffer: web_sys::WebGlBuffer = buffer.into();
+            gl_call!(
+                &self.gl,
+                bind_buffer,
+                BufferKind::ElementArray as u32,
+                Some(&buffer)
+            );
         }
     }
 
@@ -254,29 +588,149 @@ impl GLContext {
         gl_call!(&self.gl, draw_arrays, mode as u32, 0, count as i32);
     }
 
+    pub fn draw_arrays_instanced(&self, mode: Primitives, count: usize, instances: usize) {
+        if let Some(gl) = self.webgl2("draw_arrays_instanced") {
+            gl.draw_arrays_instanced(mode as u32, 0, count as i32, instances as i32);
+        }
+    }
+
+    pub fn draw_elements_instanced(
+        &self,
+        mode: Primitives,
+        count: usize,
+        kind: DataType,
+        offset: u32,
+        instances: usize,
+    ) {
+        debug_check_index_offset("draw_elements_instanced", kind, offset);
+        if let Some(gl) = self.webgl2("draw_elements_instanced") {
+            gl.draw_elements_instanced_with_i32(
+                mode as u32,
+                count as i32,
+                kind as u32,
+                offset as i32,
+                instances as i32,
+            );
+        }
+    }
+
+    pub fn draw_range_elements(
+        &self,
+        mode: Primitives,
+        start: u32,
+        end: u32,
+        count: usize,
+        kind: DataType,
+        offset: u32,
+    ) {
+        debug_check_index_offset("draw_range_elements", kind, offset);
+        match &self.gl {
+            WebContext::Gl2(gl) => gl.draw_range_elements_with_i32(
+                mode as u32,
+                start,
+                end,
+                count as i32,
+                kind as u32,
+                offset as i32,
+            ),
+            // the range is only an optimization hint
+            WebContext::Gl(_) => self.draw_elements(mode, count, kind, offset),
+        }
+    }
+
+    pub fn vertex_attrib_divisor(&self, location: u32, divisor: u32) {
+        if let Some(gl) = self.webgl2("vertex_attrib_divisor") {
+            gl.vertex_attrib_divisor(location, divisor);
+        }
+    }
+
+    pub fn vertex_attrib_format(
+        &self,
+        _location: u32,
+        _size: AttributeSize,
+        _kind: DataType,
+        _normalized: bool,
+        _relative_offset: u32,
+    ) {
+        // separate attribute formats are OpenGL ES 3.1, WebGL 2.0 stops at ES 3.0
+        self.log_at(
+            LogLevel::Error,
+            "ERROR vertex_attrib_format is not supported on WebGL",
+        );
+    }
+
+    pub fn vertex_attrib_binding(&self, _location: u32, _binding_index: u32) {
+        self.log_at(
+            LogLevel::Error,
+            "ERROR vertex_attrib_binding is not supported on WebGL",
+        );
+    }
+
+    pub fn bind_vertex_buffer(
+        &self,
+        _binding_index: u32,
+        _buffer: &WebGLBuffer,
+        _offset: usize,
+        _stride: u32,
+    ) {
+        self.log_at(
+            LogLevel::Error,
+            "ERROR bind_vertex_buffer is not supported on WebGL",
+        );
+    }
+
     fn check_error(&self, msg: &str) {
         let code = gl_call!(&self.gl, get_error);
         if code != web_sys::WebGl2RenderingContext::NO_ERROR {
-            print(&format!(
-                "ERROR {} {}",
-                msg,
-                match code {
-                    web_sys::WebGl2RenderingContext::INVALID_ENUM => "invalid enum",
-                    web_sys::WebGl2RenderingContext::INVALID_OPERATION => "invalid operation",
-                    web_sys::WebGl2RenderingContext::INVALID_VALUE => "invalid value",
-                    web_sys::WebGl2RenderingContext::OUT_OF_MEMORY => "out of memory",
-                    web_sys::WebGl2RenderingContext::INVALID_FRAMEBUFFER_OPERATION =>
-                        "invalid framebuffer operation",
-                    web_sys::WebGl2RenderingContext::CONTEXT_LOST_WEBGL => "context lost webgl",
-                    _ => "unknown error",
-                },
-            ));
+            self.log_at(
+                LogLevel::Error,
+                format!(
+                    "ERROR {} {}",
+                    msg,
+                    match code {
+                        web_sys::WebGl2RenderingContext::INVALID_ENUM => "invalid enum",
+                        web_sys::WebGl2RenderingContext::INVALID_OPERATION => "invalid operation",
+                        web_sys::WebGl2RenderingContext::INVALID_VALUE => "invalid value",
+                        web_sys::WebGl2RenderingContext::OUT_OF_MEMORY => "out of memory",
+                        web_sys::WebGl2RenderingContext::INVALID_FRAMEBUFFER_OPERATION =>
+                            "invalid framebuffer operation",
+                        web_sys::WebGl2RenderingContext::CONTEXT_LOST_WEBGL => "context lost webgl",
+                        _ => "unknown error",
+                    },
+                ),
+            );
         }
     }
 
+    pub(crate) fn get_error(&self) -> u32 {
+        gl_call!(&self.gl, get_error)
+    }
+
+    /// create a shader object.
+    ///
+    /// WebGL has no geometry or tessellation shaders: asking for one logs an error and
+    /// returns a shader that fails to compile with the same message, and so the program
+    /// it is attached to fails to link.
     pub fn create_shader(&self, kind: ShaderKind) -> WebGLShader {
-        if let Some(val) = gl_call!(&self.gl, create_shader, kind as u32) {
+        let unsupported = match kind {
+            ShaderKind::Geometry | ShaderKind::TessControl | ShaderKind::TessEvaluation => {
+                let msg = format!("{:?} shaders are not supported on WebGL", kind);
+                self.log_at(LogLevel::Error, format!("ERROR create_shader : {}", msg));
+                Some(msg)
+            }
+            _ => None,
+        };
+        // an unsupported shader still needs an object to attach to programs
+        let created_kind = if unsupported.is_some() {
+            ShaderKind::Vertex
+        } else {
+            kind
+        };
+        if let Some(val) = gl_call!(&self.gl, create_shader, created_kind as u32) {
             let id = self.add(val.into());
+            if let Some(msg) = unsupported {
+                self.unsupported_shaders.borrow_mut().insert(id, msg);
+            }
             return WebGLShader(id);
         }
         self.check_error("create_shader");
@@ -286,7 +740,19 @@ impl GLContext {
     pub fn shader_source(&self, shader: &WebGLShader, code: &str) {
         let shader: web_sys::WebGlShader = self.get(shader.0).unwrap().into();
         gl_call!(&self.gl, shader_source, &shader, code);
-        self.log(&format!("shader source:\n{}", code));
+        self.log_at(LogLevel::Debug, format!("shader source:\n{}", code));
+    }
+
+    pub fn shader_source_multi(&self, shader: &WebGLShader, sources: &[&str]) {
+        // WebGL only accepts one string, keep each source on its own lines
+        let mut code = String::new();
+        for source in sources {
+            code.push_str(source);
+            if !source.ends_with('\n') {
+                code.push('\n');
+            }
+        }
+        self.shader_source(shader, &code);
     }
 
     pub fn create_program(&self) -> WebGLProgram {
@@ -295,6 +761,14 @@ impl GLContext {
     }
 
     pub fn link_program(&self, program: &WebGLProgram) {
+        if let Err(log) = self.try_link_program(program) {
+            self.log_at(LogLevel::Error, "ERROR while linking program :");
+            self.log_at(LogLevel::Error, log);
+        }
+    }
+
+    pub(crate) fn try_link_program(&self, program: &WebGLProgram) -> Result<(), String> {
+        self.forget_uniform_locations(program);
         let program: web_sys::WebGlProgram = self.get(program.0).unwrap().into();
         gl_call!(&self.gl, link_program, &program);
         let result = gl_call!(
@@ -304,12 +778,17 @@ impl GLContext {
             web_sys::WebGl2RenderingContext::LINK_STATUS
         );
         if !result {
-            print("ERROR while linking program :");
-            print(&format!(
-                "{}",
-                gl_call!(&self.gl, get_program_info_log, &program).unwrap()
-            ));
+            return Err(gl_call!(&self.gl, get_program_info_log, &program).unwrap());
         }
+        Ok(())
+    }
+
+    pub fn delete_program(&self, program: &WebGLProgram) {
+        self.forget_uniform_locations(program);
+        // the uniform locations of the program are forgotten with it
+        self.delete_object(program.0, |program: &web_sys::WebGlProgram| {
+            gl_call!(&self.gl, delete_program, Some(program))
+        });
     }
 
     pub fn attach_shader(&self, program: &WebGLProgram, shader: &WebGLShader) {
@@ -318,11 +797,44 @@ impl GLContext {
         gl_call!(&self.gl, attach_shader, &program, &shader);
     }
 
+    pub fn detach_shader(&self, program: &WebGLProgram, shader: &WebGLShader) {
+        let id = shader.0;
+        let program: web_sys::WebGlProgram = self.get(program.0).unwrap().into();
+        let shader: web_sys::WebGlShader = self.get(id).unwrap().into();
+        let deleted = gl_call!(
+            &self.gl,
+            get_shader_parameter,
+            &shader,
+            web_sys::WebGl2RenderingContext::DELETE_STATUS
+        )
+        .as_bool()
+        .unwrap_or(false);
+        gl_call!(&self.gl, detach_shader, &program, &shader);
+        if deleted {
+            // the handle was given again by get_attached_shaders after the shader was deleted
+            self.delete_object(id, |_: &JsValue| ());
+        }
+    }
+
+    pub fn get_attached_shaders(&self, program: &WebGLProgram) -> Vec<WebGLShader> {
+        let program: web_sys::WebGlProgram = self.get(program.0).unwrap().into();
+        let shaders = match gl_call!(&self.gl, get_attached_shaders, &program) {
+            Some(shaders) => shaders,
+            None => return Vec::new(),
+        };
+        shaders
+            .iter()
+            .map(|shader| {
+                // reuse the handle of a shader we already know
+                WebGLShader(self.objects.find_or_add(shader))
+            })
+            .collect()
+    }
+
     pub fn delete_buffer(&self, buffer: &WebGLBuffer) {
-        let id = buffer.0;
-        let buffer: web_sys::WebGlBuffer = self.get(id).unwrap().into();
-        gl_call!(&self.gl, delete_buffer, Some(&buffer));
-        self.remove(id);
+        self.delete_object(buffer.0, |buffer: &web_sys::WebGlBuffer| {
+            gl_call!(&self.gl, delete_buffer, Some(buffer))
+        });
     }
 
     pub fn unbind_buffer(&self, kind: BufferKind) {
@@ -334,25 +846,120 @@ impl GLContext {
         gl_call!(&self.gl, bind_attrib_location, &program, loc, name);
     }
 
+    pub fn bind_frag_data_location(&self, _program: &WebGLProgram, color_number: u32, name: &str) {
+        // not available in WebGL, outputs are bound with layout(location=) qualifiers
+        self.log_at(
+            LogLevel::Info,
+            format!(
+            "bind_frag_data_location is a no-op on WebGL, use layout(location = {}) on output {} instead",
+            color_number, name
+        ),
+        );
+    }
+
+    pub fn get_frag_data_location(&self, program: &WebGLProgram, name: &str) -> Option<i32> {
+        match &self.gl {
+            WebContext::Gl2(gl) => {
+                let program: web_sys::WebGlProgram = self.get(program.0).unwrap().into();
+                let loc = gl.get_frag_data_location(&program, name);
+                if loc == -1 {
+                    None
+                } else {
+                    Some(loc)
+                }
+            }
+            WebContext::Gl(_) => None, // not supported on webgl
+        }
+    }
+
     pub fn get_uniform_location(
         &self,
         program: &WebGLProgram,
         name: &str,
     ) -> Option<WebGLUniformLocation> {
-        let program: web_sys::WebGlProgram = self.get(program.0).unwrap().into();
+        let id = program.0;
+        let program: web_sys::WebGlProgram = self.get(id).unwrap().into();
         let val = gl_call!(&self.gl, get_uniform_location, &program, name);
         val.map(|v| WebGLUniformLocation {
-            reference: self.add(v.into()),
+            reference: self.objects.add_owned(id, v.into()),
             name: name.to_string(),
         })
     }
 
+    // getUniform returns a number, a boolean or an array depending on the uniform type
+    fn get_uniform(&self, program: &WebGLProgram, location: &WebGLUniformLocation) -> Vec<f64> {
+        let program: web_sys::WebGlProgram = self.get(program.0).unwrap().into();
+        let location: web_sys::WebGlUniformLocation = self.get(location.reference).unwrap().into();
+        let val = gl_call!(&self.gl, get_uniform, &program, &location);
+        let to_f64 = |v: &JsValue| match v.as_bool() {
+            Some(b) => b as u8 as f64,
+            None => v.as_f64().unwrap_or(0.0),
+        };
+        if val.is_object() {
+            Array::from(&val).iter().map(|v| to_f64(&v)).collect()
+        } else {
+            vec![to_f64(&val)]
+        }
+    }
+
+    pub fn get_uniform_f32(
+        &self,
+        program: &WebGLProgram,
+        location: &WebGLUniformLocation,
+    ) -> Vec<f32> {
+        self.get_uniform(program, location)
+            .into_iter()
+            .map(|v| v as f32)
+            .collect()
+    }
+
+    pub fn get_uniform_i32(
+        &self,
+        program: &WebGLProgram,
+        location: &WebGLUniformLocation,
+    ) -> Vec<i32> {
+        self.get_uniform(program, location)
+            .into_iter()
+            .map(|v| v as i32)
+            .collect()
+    }
+
+    // WebGL 1.0 rejects the WebGL 2.0 capabilities with an invalid enum error
+    fn is_flag_supported(&self, flag: i32, context: &str) -> bool {
+        if !self.is_webgl2 && flag == Flag::RasterizerDiscard as i32 {
+            self.log_at(
+                LogLevel::Error,
+                format!("ERROR {} : rasterizer discard requires WebGL 2.0", context),
+            );
+            return false;
+        }
+        if flag == Flag::SampleShading as i32
+            || flag == Flag::SampleMask as i32
+            || flag == Flag::DepthBoundsTest as i32
+        {
+            self.log_at(
+                LogLevel::Error,
+                format!("ERROR {} : {:#x} is not supported on WebGL", context, flag),
+            );
+            return false;
+        }
+        true
+    }
+
     pub fn enable(&self, flag: i32) {
-        gl_call!(&self.gl, enable, flag as u32);
+        if self.is_flag_supported(flag, "enable") && self.update_flag_cache(flag, true) {
+            gl_call!(&self.gl, enable, flag as u32);
+        }
     }
 
     pub fn disable(&self, flag: i32) {
-        gl_call!(&self.gl, disable, flag as u32);
+        if self.is_flag_supported(flag, "disable") && self.update_flag_cache(flag, false) {
+            gl_call!(&self.gl, disable, flag as u32);
+        }
+    }
+
+    pub fn is_enabled(&self, flag: Flag) -> bool {
+        gl_call!(&self.gl, is_enabled, flag as u32)
     }
 
     pub fn cull_face(&self, flag: Culling) {
@@ -363,58 +970,207 @@ impl GLContext {
         gl_call!(&self.gl, depth_mask, is_on);
     }
 
-    pub fn depth_func(&self, d: DepthTest) {
+    pub fn color_mask(&self, r: bool, g: bool, b: bool, a: bool) {
+        gl_call!(&self.gl, color_mask, r, g, b, a);
+    }
+
+    pub fn depth_func(&self, d: CompareFunc) {
         gl_call!(&self.gl, depth_func, d as u32);
     }
 
+    pub fn get_depth_func(&self) -> Result<CompareFunc, u32> {
+        let func = self.get_parameter_i32(Parameter::DepthFunc as u32);
+        CompareFunc::try_from(func as u32)
+    }
+
+    pub fn get_blend_func(&self) -> Result<(BlendMode, BlendMode), u32> {
+        let src = self.get_parameter_i32(Parameter::BlendSrcRgb as u32);
+        let dst = self.get_parameter_i32(Parameter::BlendDstRgb as u32);
+        Ok((
+            BlendMode::try_from(src as u32)?,
+            BlendMode::try_from(dst as u32)?,
+        ))
+    }
+
     pub fn clear_depth(&self, value: f32) {
         gl_call!(&self.gl, clear_depth, value);
     }
 
-    pub fn viewport(&self, x: i32, y: i32, width: u32, height: u32) {
-        gl_call!(&self.gl, viewport, x, y, width as i32, height as i32);
+    // webgl only supports single precision depth values
+    pub fn clear_depth_f64(&self, value: f64) {
+        self.clear_depth(value as f32);
     }
 
-    pub fn draw_elements(&self, mode: Primitives, count: usize, kind: DataType, offset: u32) {
-        gl_call!(
-            &self.gl,
-            draw_elements_with_i32,
-            mode as u32,
-            count as i32,
-            kind as u32,
-            offset as i32
-        );
+    pub fn depth_range(&self, near: f32, far: f32) {
+        gl_call!(&self.gl, depth_range, near, far);
     }
 
-    pub fn generate_mipmap(&self) {
-        gl_call!(
-            &self.gl,
-            generate_mipmap,
-            web_sys::WebGl2RenderingContext::TEXTURE_2D
+    pub fn depth_range_f64(&self, near: f64, far: f64) {
+        self.depth_range(near as f32, far as f32);
+    }
+
+    pub fn viewport(&self, x: i32, y: i32, width: u32, height: u32) {
+        gl_call!(&self.gl, viewport, x, y, width as i32, height as i32);
+    }
+
+    pub fn viewport_indexed(&self, _index: u32, _rect: Rect) {
+        self.log_at(
+            LogLevel::Error,
+            "ERROR viewport_indexed is not supported on WebGL",
         );
     }
 
-    pub fn generate_mipmap_cube(&self) {
-        gl_call!(
-            &self.gl,
-            generate_mipmap,
-            web_sys::WebGl2RenderingContext::TEXTURE_CUBE_MAP
+    pub fn viewport_array(&self, _rects: &[Rect]) {
+        self.log_at(
+            LogLevel::Error,
+            "ERROR viewport_array is not supported on WebGL",
         );
     }
 
-    pub fn create_texture(&self) -> WebGLTexture {
-        let val = gl_call!(&self.gl, create_texture);
-        WebGLTexture(self.add(val.into()))
+    pub fn resize(&self, width: u32, height: u32) {
+        // the backing store must be resized too, not only the CSS size
+        if let Some(canvas) = gl_call!(&self.gl, canvas) {
+            if let Ok(canvas) = canvas.dyn_into::<HtmlCanvasElement>() {
+                canvas.set_width(width);
+                canvas.set_height(height);
+            }
+        }
+        self.viewport(0, 0, width, height);
     }
 
-    pub fn delete_texture(&self, texture: &WebGLTexture) {
-        let id = texture.0;
-        let texture: web_sys::WebGlTexture = self.get(id).unwrap().into();
-        gl_call!(&self.gl, delete_texture, Some(&texture));
-        self.remove(id);
+    pub fn is_context_lost(&self) -> bool {
+        gl_call!(&self.gl, is_context_lost)
+    }
+
+    pub fn get_context_attributes(&self) -> ContextAttributes {
+        let attributes: JsValue = match gl_call!(&self.gl, get_context_attributes) {
+            Some(attributes) => attributes.into(),
+            // the context is lost
+            None => return ContextAttributes::default(),
+        };
+        let get = |name: &str| {
+            Reflect::get(&attributes, &JsValue::from_str(name))
+                .ok()
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false)
+        };
+        ContextAttributes {
+            alpha: get("alpha"),
+            depth: get("depth"),
+            stencil: get("stencil"),
+            antialias: get("antialias"),
+            premultiplied_alpha: get("premultipliedAlpha"),
+            preserve_drawing_buffer: get("preserveDrawingBuffer"),
+        }
+    }
+
+    // the canvas size in device pixels, which is what viewport expects on HiDPI displays
+    pub fn drawing_buffer_width(&self) -> u32 {
+        gl_call!(&self.gl, drawing_buffer_width) as u32
+    }
+
+    pub fn drawing_buffer_height(&self) -> u32 {
+        gl_call!(&self.gl, drawing_buffer_height) as u32
+    }
+
+    pub fn patch_parameter_vertices(&self, _n: i32) {
+        // no tessellation on WebGL
+        self.log_at(
+            LogLevel::Error,
+            "ERROR patch_parameter_vertices is not supported on WebGL",
+        );
+    }
+
+    pub fn primitive_restart_index(&self, _index: u32) {
+        // WebGL 2.0 always restarts primitives at the maximum value of the index type
+        self.log_at(
+            LogLevel::Error,
+            "ERROR primitive_restart_index is not supported on WebGL",
+        );
+    }
+
+    pub fn min_sample_shading(&self, _value: f32) {
+        // per-sample shading does not exist in WebGL
+        self.log_at(
+            LogLevel::Error,
+            "ERROR min_sample_shading is not supported on WebGL",
+        );
+    }
+
+    pub fn clamp_read_color(&self, clamp: bool) {
+        // WebGL never clamps the colors read from float framebuffers
+        if clamp {
+            self.log_at(
+                LogLevel::Error,
+                "ERROR clamp_read_color is not supported on WebGL",
+            );
+        }
+    }
+
+    pub fn depth_bounds(&self, _min: f32, _max: f32) {
+        self.log_at(
+            LogLevel::Error,
+            "ERROR depth_bounds is not supported on WebGL",
+        );
+    }
+
+    pub fn sample_mask(&self, _index: u32, _mask: u32) {
+        self.log_at(
+            LogLevel::Error,
+            "ERROR sample_mask is not supported on WebGL",
+        );
+    }
+
+    pub fn scissor(&self, x: i32, y: i32, width: u32, height: u32) {
+        gl_call!(&self.gl, scissor, x, y, width as i32, height as i32);
+    }
+
+    pub fn draw_elements(&self, mode: Primitives, count: usize, kind: DataType, offset: u32) {
+        debug_check_index_offset("draw_elements", kind, offset);
+        gl_call!(
+            &self.gl,
+            draw_elements_with_i32,
+            mode as u32,
+            count as i32,
+            kind as u32,
+            offset as i32
+        );
+    }
+
+    pub fn generate_mipmap(&self) {
+        gl_call!(
+            &self.gl,
+            generate_mipmap,
+            web_sys::WebGl2RenderingContext::TEXTURE_2D
+        );
+    }
+
+    pub fn generate_mipmap_cube(&self) {
+        gl_call!(
+            &self.gl,
+            generate_mipmap,
+            web_sys::WebGl2RenderingContext::TEXTURE_CUBE_MAP
+        );
+    }
+
+    pub fn create_texture(&self) -> WebGLTexture {
+        let val = gl_call!(&self.gl, create_texture);
+        WebGLTexture(self.add(val.into()))
+    }
+
+    pub fn delete_texture(&self, texture: &WebGLTexture) {
+        self.delete_object(texture.0, |texture: &web_sys::WebGlTexture| {
+            gl_call!(&self.gl, delete_texture, Some(texture))
+        });
     }
 
     pub fn active_texture(&self, active: u32) {
+        debug_assert!(
+            active < self.max_combined_texture_units(),
+            "active_texture : texture unit {} is out of range, the implementation supports {} units",
+            active,
+            self.max_combined_texture_units()
+        );
         gl_call!(
             &self.gl,
             active_texture,
@@ -422,6 +1178,15 @@ impl GLContext {
         );
     }
 
+    pub fn max_combined_texture_units(&self) -> u32 {
+        self.get_parameter_i32(Parameter::MaxCombinedTextureImageUnits as u32) as u32
+    }
+
+    pub fn get_active_texture(&self) -> u32 {
+        self.get_parameter_i32(Parameter::ActiveTexture as u32) as u32
+            - web_sys::WebGl2RenderingContext::TEXTURE0
+    }
+
     pub fn bind_texture(&self, texture: &WebGLTexture) {
         let texture: web_sys::WebGlTexture = self.get(texture.0).unwrap().into();
         gl_call!(
@@ -463,6 +1228,30 @@ impl GLContext {
         gl_call!(&self.gl, blend_func, sfactor as u32, dfactor as u32);
     }
 
+    // per draw buffer blending requires OES_draw_buffers_indexed, not exposed here
+    fn indexed_unsupported(&self, name: &str) {
+        self.log_at(
+            LogLevel::Error,
+            format!("ERROR {} is not supported on WebGL", name),
+        );
+    }
+
+    pub fn blend_equation_indexed(&self, _buf: u32, _eq: BlendEquation) {
+        self.indexed_unsupported("blend_equation_indexed");
+    }
+
+    pub fn blend_func_indexed(&self, _buf: u32, _src: BlendMode, _dst: BlendMode) {
+        self.indexed_unsupported("blend_func_indexed");
+    }
+
+    pub fn enable_indexed(&self, _flag: Flag, _buf: u32) {
+        self.indexed_unsupported("enable_indexed");
+    }
+
+    pub fn disable_indexed(&self, _flag: Flag, _buf: u32) {
+        self.indexed_unsupported("disable_indexed");
+    }
+
     pub fn blend_color(&self, r: f32, g: f32, b: f32, a: f32) {
         gl_call!(&self.gl, blend_color, r, g, b, a);
     }
@@ -473,10 +1262,9 @@ impl GLContext {
     }
 
     pub fn delete_framebuffer(&self, fb: &WebGLFrameBuffer) {
-        let id = fb.0;
-        let fb: web_sys::WebGlFramebuffer = self.get(id).unwrap().into();
-        gl_call!(&self.gl, delete_framebuffer, Some(&fb));
-        self.remove(id);
+        self.delete_object(fb.0, |fb: &web_sys::WebGlFramebuffer| {
+            gl_call!(&self.gl, delete_framebuffer, Some(fb))
+        });
     }
 
     pub fn bind_framebuffer(&self, buffer: Buffers, fb: &WebGLFrameBuffer) {
@@ -504,22 +1292,155 @@ impl GLContext {
         );
     }
 
+    pub fn framebuffer_texture2d_multisample(
+        &self,
+        target: Buffers,
+        attachment: Buffers,
+        textarget: TextureBindPoint,
+        texture: &WebGLTexture,
+        level: i32,
+        samples: i32,
+    ) {
+        // no browser exposes EXT_multisampled_render_to_texture yet, web-sys has no binding for it
+        let ext = match gl_call!(
+            &self.gl,
+            get_extension,
+            "EXT_multisampled_render_to_texture"
+        ) {
+            Ok(Some(ext)) => ext,
+            _ => {
+                self.log_at(
+                    LogLevel::Error,
+                    "ERROR framebuffer_texture2d_multisample requires EXT_multisampled_render_to_texture",
+                );
+                return;
+            }
+        };
+        let attach: js_sys::Function = Reflect::get(
+            &ext,
+            &JsValue::from_str("framebufferTexture2DMultisampleEXT"),
+        )
+        .unwrap()
+        .unchecked_into();
+        let texture: JsValue = self.get(texture.0).unwrap();
+        let args = Array::of5(
+            &JsValue::from(target as u32),
+            &JsValue::from(attachment as u32),
+            &JsValue::from(textarget as u32),
+            &texture,
+            &JsValue::from(level),
+        );
+        args.push(&JsValue::from(samples));
+        attach.apply(&ext, &args).unwrap();
+    }
+
     pub fn unbind_framebuffer(&self, buffer: Buffers) {
         gl_call!(&self.gl, bind_framebuffer, buffer as u32, None);
     }
 
-    pub fn tex_parameteri(&self, kind: TextureKind, pname: TextureParameter, param: i32) {
-        // skip not supported flag in for webgl 1 context
-        if !self.is_webgl2 {
-            if let TextureParameter::TextureWrapR = pname {
-                return;
+    pub fn check_framebuffer_status(&self, target: Buffers) -> FramebufferStatus {
+        let status = gl_call!(&self.gl, check_framebuffer_status, target as u32);
+        FramebufferStatus::from(status)
+    }
+
+    pub fn create_renderbuffer(&self) -> WebGLRenderBuffer {
+        let val = gl_call!(&self.gl, create_renderbuffer).unwrap();
+        WebGLRenderBuffer(self.add(val.into()))
+    }
+
+    pub fn delete_renderbuffer(&self, rb: &WebGLRenderBuffer) {
+        self.delete_object(rb.0, |rb: &web_sys::WebGlRenderbuffer| {
+            gl_call!(&self.gl, delete_renderbuffer, Some(rb))
+        });
+    }
+
+    pub fn bind_renderbuffer(&self, rb: &WebGLRenderBuffer) {
+        let rb: web_sys::WebGlRenderbuffer = self.get(rb.0).unwrap().into();
+        gl_call!(
+            &self.gl,
+            bind_renderbuffer,
+            web_sys::WebGl2RenderingContext::RENDERBUFFER,
+            Some(&rb)
+        );
+    }
+
+    pub fn unbind_renderbuffer(&self) {
+        gl_call!(
+            &self.gl,
+            bind_renderbuffer,
+            web_sys::WebGl2RenderingContext::RENDERBUFFER,
+            None
+        );
+    }
+
+    pub fn renderbuffer_storage(&self, internal_format: PixelFormat, width: u16, height: u16) {
+        gl_call!(
+            &self.gl,
+            renderbuffer_storage,
+            web_sys::WebGl2RenderingContext::RENDERBUFFER,
+            internal_format as u32,
+            width as i32,
+            height as i32
+        );
+    }
+
+    pub fn framebuffer_renderbuffer(
+        &self,
+        target: Buffers,
+        attachment: Buffers,
+        rb: &WebGLRenderBuffer,
+    ) {
+        let rb: web_sys::WebGlRenderbuffer = self.get(rb.0).unwrap().into();
+        gl_call!(
+            &self.gl,
+            framebuffer_renderbuffer,
+            target as u32,
+            attachment as u32,
+            web_sys::WebGl2RenderingContext::RENDERBUFFER,
+            Some(&rb)
+        );
+    }
+
+    // whether the texture parameter exists in this context
+    fn is_tex_parameter_supported(&self, pname: TextureParameter, context: &str) -> bool {
+        match pname {
+            // skip not supported flag in for webgl 1 context
+            TextureParameter::TextureWrapR => self.is_webgl2,
+            TextureParameter::TextureBaseLevel
+            | TextureParameter::TextureMaxLevel
+            | TextureParameter::TextureCompareMode
+            | TextureParameter::TextureCompareFunc
+            | TextureParameter::TextureMinLod
+            | TextureParameter::TextureMaxLod
+                if !self.is_webgl2 =>
+            {
+                self.log_at(
+                    LogLevel::Error,
+                    format!("ERROR {} : {:?} requires WebGL 2.0", context, pname),
+                );
+                false
+            }
+            TextureParameter::TextureLodBias => {
+                self.log_at(
+                    LogLevel::Error,
+                    format!("ERROR {} : {:?} is not supported on WebGL", context, pname),
+                );
+                false
             }
+            _ => true,
+        }
+    }
+
+    pub fn tex_parameteri(&self, kind: TextureKind, pname: TextureParameter, param: i32) {
+        if self.is_tex_parameter_supported(pname, "tex_parameteri") {
+            gl_call!(&self.gl, tex_parameteri, kind as u32, pname as u32, param);
         }
-        gl_call!(&self.gl, tex_parameteri, kind as u32, pname as u32, param);
     }
 
     pub fn tex_parameterfv(&self, kind: TextureKind, pname: TextureParameter, param: f32) {
-        gl_call!(&self.gl, tex_parameterf, kind as u32, pname as u32, param);
+        if self.is_tex_parameter_supported(pname, "tex_parameterfv") {
+            gl_call!(&self.gl, tex_parameterf, kind as u32, pname as u32, param);
+        }
     }
 
     pub fn draw_buffer(&self, buffers: &[ColorBuffer]) {
@@ -536,6 +1457,7 @@ impl GLContext {
     }
 
     pub fn uniform_matrix_3fv(&self, location: &WebGLUniformLocation, value: &[[f32; 3]; 3]) {
+        self.debug_check_program_in_use("uniform_matrix_3fv");
         use std::mem;
         let array = unsafe { mem::transmute::<&[[f32; 3]; 3], &[f32; 9]>(value) as &[f32] };
         let location: web_sys::WebGlUniformLocation = self.get(location.reference).unwrap().into();
@@ -549,6 +1471,7 @@ impl GLContext {
     }
 
     pub fn uniform_matrix_2fv(&self, location: &WebGLUniformLocation, value: &[[f32; 2]; 2]) {
+        self.debug_check_program_in_use("uniform_matrix_2fv");
         use std::mem;
         let array = unsafe { mem::transmute::<&[[f32; 2]; 2], &[f32; 4]>(value) as &[f32] };
         let location: web_sys::WebGlUniformLocation = self.get(location.reference).unwrap().into();
@@ -561,22 +1484,80 @@ impl GLContext {
         );
     }
 
+    pub fn uniform_matrix_2x3fv(&self, location: &WebGLUniformLocation, value: &[f32]) {
+        self.debug_check_program_in_use("uniform_matrix_2x3fv");
+        if let Some(gl) = self.webgl2("uniform_matrix_2x3fv") {
+            let location: web_sys::WebGlUniformLocation =
+                self.get(location.reference).unwrap().into();
+            gl.uniform_matrix2x3fv_with_f32_array(Some(&location), false, value);
+        }
+    }
+
+    pub fn uniform_matrix_3x2fv(&self, location: &WebGLUniformLocation, value: &[f32]) {
+        self.debug_check_program_in_use("uniform_matrix_3x2fv");
+        if let Some(gl) = self.webgl2("uniform_matrix_3x2fv") {
+            let location: web_sys::WebGlUniformLocation =
+                self.get(location.reference).unwrap().into();
+            gl.uniform_matrix3x2fv_with_f32_array(Some(&location), false, value);
+        }
+    }
+
+    pub fn uniform_matrix_2x4fv(&self, location: &WebGLUniformLocation, value: &[f32]) {
+        self.debug_check_program_in_use("uniform_matrix_2x4fv");
+        if let Some(gl) = self.webgl2("uniform_matrix_2x4fv") {
+            let location: web_sys::WebGlUniformLocation =
+                self.get(location.reference).unwrap().into();
+            gl.uniform_matrix2x4fv_with_f32_array(Some(&location), false, value);
+        }
+    }
+
+    pub fn uniform_matrix_4x2fv(&self, location: &WebGLUniformLocation, value: &[f32]) {
+        self.debug_check_program_in_use("uniform_matrix_4x2fv");
+        if let Some(gl) = self.webgl2("uniform_matrix_4x2fv") {
+            let location: web_sys::WebGlUniformLocation =
+                self.get(location.reference).unwrap().into();
+            gl.uniform_matrix4x2fv_with_f32_array(Some(&location), false, value);
+        }
+    }
+
+    pub fn uniform_matrix_3x4fv(&self, location: &WebGLUniformLocation, value: &[f32]) {
+        self.debug_check_program_in_use("uniform_matrix_3x4fv");
+        if let Some(gl) = self.webgl2("uniform_matrix_3x4fv") {
+            let location: web_sys::WebGlUniformLocation =
+                self.get(location.reference).unwrap().into();
+            gl.uniform_matrix3x4fv_with_f32_array(Some(&location), false, value);
+        }
+    }
+
+    pub fn uniform_matrix_4x3fv(&self, location: &WebGLUniformLocation, value: &[f32]) {
+        self.debug_check_program_in_use("uniform_matrix_4x3fv");
+        if let Some(gl) = self.webgl2("uniform_matrix_4x3fv") {
+            let location: web_sys::WebGlUniformLocation =
+                self.get(location.reference).unwrap().into();
+            gl.uniform_matrix4x3fv_with_f32_array(Some(&location), false, value);
+        }
+    }
+
     pub fn uniform_1i(&self, location: &WebGLUniformLocation, value: i32) {
+        self.debug_check_program_in_use("uniform_1i");
         let location: web_sys::WebGlUniformLocation = self.get(location.reference).unwrap().into();
         gl_call!(&self.gl, uniform1i, Some(&location), value);
     }
 
     pub fn uniform_1f(&self, location: &WebGLUniformLocation, value: f32) {
+        self.debug_check_program_in_use("uniform_1f");
         let location: web_sys::WebGlUniformLocation = self.get(location.reference).unwrap().into();
         gl_call!(&self.gl, uniform1f, Some(&location), value);
     }
 
     pub fn uniform_2f(&self, location: &WebGLUniformLocation, value: (f32, f32)) {
+        self.debug_check_program_in_use("uniform_2f");
         let location: web_sys::WebGlUniformLocation = self.get(location.reference).unwrap().into();
         gl_call!(&self.gl, uniform2f, Some(&location), value.0, value.1);
     }
 
     pub fn uniform_3f(&self, location: &WebGLUniformLocation, value: (f32, f32, f32)) {
+        self.debug_check_program_in_use("uniform_3f");
         let location: web_sys::WebGlUniformLocation = self.get(location.reference).unwrap().into();
         gl_call!(
             &self.gl,
@@ -589,6 +1570,7 @@ impl GLContext {
     }
 
     pub fn uniform_4f(&self, location: &WebGLUniformLocation, value: (f32, f32, f32, f32)) {
+        self.debug_check_program_in_use("uniform_4f");
         let location: web_sys::WebGlUniformLocation = self.get(location.reference).unwrap().into();
         gl_call!(
             &self.gl,
@@ -601,7 +1583,80 @@ impl GLContext {
         );
     }
 
+    pub fn uniform_1ui(&self, location: &WebGLUniformLocation, value: u32) {
+        self.debug_check_program_in_use("uniform_1ui");
+        if let Some(gl) = self.webgl2("uniform_1ui") {
+            let location: web_sys::WebGlUniformLocation =
+                self.get(location.reference).unwrap().into();
+            gl.uniform1ui(Some(&location), value);
+        }
+    }
+
+    pub fn uniform_2ui(&self, location: &WebGLUniformLocation, value: (u32, u32)) {
+        self.debug_check_program_in_use("uniform_2ui");
+        if let Some(gl) = self.webgl2("uniform_2ui") {
+            let location: web_sys::WebGlUniformLocation =
+                self.get(location.reference).unwrap().into();
+            gl.uniform2ui(Some(&location), value.0, value.1);
+        }
+    }
+
+    pub fn uniform_3ui(&self, location: &WebGLUniformLocation, value: (u32, u32, u32)) {
+        self.debug_check_program_in_use("uniform_3ui");
+        if let Some(gl) = self.webgl2("uniform_3ui") {
+            let location: web_sys::WebGlUniformLocation =
+                self.get(location.reference).unwrap().into();
+            gl.uniform3ui(Some(&location), value.0, value.1, value.2);
+        }
+    }
+
+    pub fn uniform_4ui(&self, location: &WebGLUniformLocation, value: (u32, u32, u32, u32)) {
+        self.debug_check_program_in_use("uniform_4ui");
+        if let Some(gl) = self.webgl2("uniform_4ui") {
+            let location: web_sys::WebGlUniformLocation =
+                self.get(location.reference).unwrap().into();
+            gl.uniform4ui(Some(&location), value.0, value.1, value.2, value.3);
+        }
+    }
+
+    pub fn uniform_1uiv(&self, location: &WebGLUniformLocation, value: &[u32]) {
+        self.debug_check_program_in_use("uniform_1uiv");
+        if let Some(gl) = self.webgl2("uniform_1uiv") {
+            let location: web_sys::WebGlUniformLocation =
+                self.get(location.reference).unwrap().into();
+            gl.uniform1uiv_with_u32_array(Some(&location), value);
+        }
+    }
+
+    pub fn uniform_2uiv(&self, location: &WebGLUniformLocation, value: &[u32]) {
+        self.debug_check_program_in_use("uniform_2uiv");
+        if let Some(gl) = self.webgl2("uniform_2uiv") {
+            let location: web_sys::WebGlUniformLocation =
+                self.get(location.reference).unwrap().into();
+            gl.uniform2uiv_with_u32_array(Some(&location), value);
+        }
+    }
+
+    pub fn uniform_3uiv(&self, location: &WebGLUniformLocation, value: &[u32]) {
+        self.debug_check_program_in_use("uniform_3uiv");
+        if let Some(gl) = self.webgl2("uniform_3uiv") {
+            let location: web_sys::WebGlUniformLocation =
+                self.get(location.reference).unwrap().into();
+            gl.uniform3uiv_with_u32_array(Some(&location), value);
+        }
+    }
+
+    pub fn uniform_4uiv(&self, location: &WebGLUniformLocation, value: &[u32]) {
+        self.debug_check_program_in_use("uniform_4uiv");
+        if let Some(gl) = self.webgl2("uniform_4uiv") {
+            let location: web_sys::WebGlUniformLocation =
+                self.get(location.reference).unwrap().into();
+            gl.uniform4uiv_with_u32_array(Some(&location), value);
+        }
+    }
+
     pub fn uniform_matrix_4fv(&self, location: &WebGLUniformLocation, value: &[[f32; 4]; 4]) {
+        self.debug_check_program_in_use("uniform_matrix_4fv");
         use std::mem;
         let array = unsafe { mem::transmute::<&[[f32; 4]; 4], &[f32; 16]>(value) as &[f32] };
         let location: web_sys::WebGlUniformLocation = self.get(location.reference).unwrap().into();
@@ -618,12 +1673,15 @@ impl GLContext {
         let id = vao.0;
         match &self.gl {
             WebContext::Gl2(gl) => {
-                let vao: web_sys::WebGlVertexArrayObject = self.get(id).unwrap().into();
-                gl.delete_vertex_array(Some(&vao));
+                self.delete_object(id, |vao: &web_sys::WebGlVertexArrayObject| {
+                    gl.delete_vertex_array(Some(vao))
+                });
+            }
+            WebContext::Gl(_) => {
+                self.element_buffers.borrow_mut().remove(&id);
+                self.delete_object(id, |_: &JsValue| ());
             }
-            WebContext::Gl(_) => (), // unsupported
         }
-        self.remove(id);
     }
 
     pub fn unbind_vertex_array(&self, _vao: &WebGLVertexArray) {
@@ -631,14 +1689,109 @@ impl GLContext {
             WebContext::Gl2(gl) => {
                 gl.bind_vertex_array(None);
             }
-            WebContext::Gl(_) => (), // unsupported
+            WebContext::Gl(_) => self.restore_element_buffer(0),
+        }
+    }
+
+    pub fn shader_binary(&self, _shaders: &[&WebGLShader], _format: u32, _binary: &[u8]) {
+        // WebGL only accepts GLSL sources
+        self.log_at(
+            LogLevel::Error,
+            "ERROR shader_binary is not supported on WebGL",
+        );
+    }
+
+    pub fn specialize_shader(
+        &self,
+        _shader: &WebGLShader,
+        _entry_point: &str,
+        _constants: &[(u32, u32)],
+    ) {
+        self.log_at(
+            LogLevel::Error,
+            "ERROR specialize_shader is not supported on WebGL",
+        );
+    }
+
+    pub fn get_shader_parameter(&self, shader: &WebGLShader, pname: ShaderParameter) -> i32 {
+        let shader: web_sys::WebGlShader = self.get(shader.0).unwrap().into();
+        let val = gl_call!(&self.gl, get_shader_parameter, &shader, pname as u32);
+        match val.as_bool() {
+            Some(status) => status as i32,
+            None => val.as_f64().unwrap_or(0.0) as i32,
         }
     }
 
     pub fn get_program_parameter(&self, program: &WebGLProgram, pname: ShaderParameter) -> i32 {
         let program: web_sys::WebGlProgram = self.get(program.0).unwrap().into();
         let val = gl_call!(&self.gl, get_program_parameter, &program, pname as u32);
-        val.as_f64().unwrap() as i32
+        // status parameters are returned as booleans
+        match val.as_bool() {
+            Some(status) => status as i32,
+            None => val.as_f64().unwrap_or(0.0) as i32,
+        }
+    }
+
+    pub fn get_active_uniform_block_name(&self, program: &WebGLProgram, index: u32) -> String {
+        let gl = match self.webgl2("get_active_uniform_block_name") {
+            Some(gl) => gl,
+            None => return String::new(),
+        };
+        let program: web_sys::WebGlProgram = self.get(program.0).unwrap().into();
+        gl.get_active_uniform_block_name(&program, index)
+            .unwrap_or_default()
+    }
+
+    pub fn get_uniform_indices(&self, program: &WebGLProgram, names: &[&str]) -> Vec<Option<u32>> {
+        let gl = match self.webgl2("get_uniform_indices") {
+            Some(gl) => gl,
+            None => return vec![None; names.len()],
+        };
+        let program: web_sys::WebGlProgram = self.get(program.0).unwrap().into();
+        let names: Array = names.iter().map(|name| JsValue::from_str(name)).collect();
+        match gl.get_uniform_indices(&program, &names) {
+            Some(indices) => indices
+                .iter()
+                .map(|index| {
+                    index
+                        .as_f64()
+                        .map(|index| index as u32)
+                        .filter(|&index| index != web_sys::WebGl2RenderingContext::INVALID_INDEX)
+                })
+                .collect(),
+            None => vec![None; names.length() as usize],
+        }
+    }
+
+    pub fn get_active_uniform_block_iv(
+        &self,
+        program: &WebGLProgram,
+        index: u32,
+        pname: UniformBlockParam,
+    ) -> Vec<i32> {
+        let gl = match self.webgl2("get_active_uniform_block_iv") {
+            Some(gl) => gl,
+            None => return Vec::new(),
+        };
+        let program: web_sys::WebGlProgram = self.get(program.0).unwrap().into();
+        let val = match gl.get_active_uniform_block_parameter(&program, index, pname as u32) {
+            Ok(val) => val,
+            Err(_) => return Vec::new(),
+        };
+        // the uniform indices are returned as an Uint32Array, the referenced-by parameters as booleans
+        if let Some(flag) = val.as_bool() {
+            vec![flag as i32]
+        } else if let Some(v) = val.as_f64() {
+            vec![v as i32]
+        } else if val.is_object() {
+            js_sys::Uint32Array::from(val)
+                .to_vec()
+                .into_iter()
+                .map(|v| v as i32)
+                .collect()
+        } else {
+            Vec::new()
+        }
     }
 
     pub fn tex_image2d(
@@ -651,50 +1804,224 @@ impl GLContext {
         kind: PixelType,
         pixels: &[u8],
     ) {
-        if pixels.len() > 0 {
-            gl_call!(
-                &self.gl,
-                tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array,
-                target as u32,
-                level as i32,
-                format as i32,
-                width as i32,
-                height as i32,
-                0,
-                format as u32,
-                kind as u32,
-                Some(pixels)
-            )
-            .unwrap();
+        // TODO: It is a strange bug !!!
+        // According https://developer.mozilla.org/en-US/docs/Web/API/WebGLRenderingContext/texImage2D
+        // the format arg should be equal to internal format arg
+        // however, only DEPTH_COMPONENT16 works but not DEPTH_COMPONENT
+        let internal_format = match format {
+            PixelFormat::DepthComponent if pixels.is_empty() => PixelFormat::DepthComponent16,
+            // WEBGL_depth_texture only accepts the unsized format on WebGL 1.0
+            PixelFormat::DepthStencil if self.is_webgl2 => PixelFormat::Depth24Stencil8,
+            _ => format,
+        };
+        self.tex_image2d_ext(
+            target,
+            level,
+            internal_format,
+            width,
+            height,
+            format,
+            kind,
+            pixels,
+        );
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn tex_image2d_ext(
+        &self,
+        target: TextureBindPoint,
+        level: u8,
+        internal_format: PixelFormat,
+        width: u16,
+        height: u16,
+        format: PixelFormat,
+        kind: PixelType,
+        pixels: &[u8],
+    ) {
+        let pixels = if pixels.is_empty() {
+            None
         } else {
-            // TODO: It is a strange bug !!!
-            // According https://developer.mozilla.org/en-US/docs/Web/API/WebGLRenderingContext/texImage2D
-            // the format arg should be equal to internal format arg
-            // however, only DEPTH_COMPONENT16 works but not DEPTH_COMPONENT
-
-            let internal_format = match format {
-                PixelFormat::DepthComponent => web_sys::WebGl2RenderingContext::DEPTH_COMPONENT16,
-                _ => format as u32,
-            };
-            gl_call!(
-                &self.gl,
-                tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array,
-                target as u32,
-                level as i32,
-                internal_format as i32,
-                width as i32,
-                height as i32,
-                0,
-                format as u32,
-                kind as u32,
-                None
-            )
-            .unwrap();
+            Some(pixels)
+        };
+        gl_call!(
+            &self.gl,
+            tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array,
+            target as u32,
+            level as i32,
+            internal_format as i32,
+            width as i32,
+            height as i32,
+            0,
+            format as u32,
+            kind as u32,
+            pixels
+        )
+        .unwrap();
+    }
+
+    pub fn tex_image2d_u16(
+        &self,
+        target: TextureBindPoint,
+        level: u8,
+        internal_format: PixelFormat,
+        width: u16,
+        height: u16,
+        format: PixelFormat,
+        kind: PixelType,
+        pixels: &[u16],
+    ) {
+        // the u8 overload would be read as bytes, the typed array gives WebGL the component type
+        let pixels = if pixels.is_empty() {
+            None
+        } else {
+            Some(Object::from(Uint16Array::from(pixels)))
+        };
+        self.tex_image2d_view(
+            target,
+            level,
+            internal_format,
+            width,
+            height,
+            format,
+            kind,
+            pixels.as_ref(),
+        );
+    }
+
+    pub fn tex_image2d_f32(
+        &self,
+        target: TextureBindPoint,
+        level: u8,
+        internal_format: PixelFormat,
+        width: u16,
+        height: u16,
+        format: PixelFormat,
+        pixels: &[f32],
+    ) {
+        let pixels = if pixels.is_empty() {
+            None
+        } else {
+            Some(Object::from(Float32Array::from(pixels)))
+        };
+        self.tex_image2d_view(
+            target,
+            level,
+            internal_format,
+            width,
+            height,
+            format,
+            PixelType::Float,
+            pixels.as_ref(),
+        );
+    }
+
+    fn tex_image2d_view(
+        &self,
+        target: TextureBindPoint,
+        level: u8,
+        internal_format: PixelFormat,
+        width: u16,
+        height: u16,
+        format: PixelFormat,
+        kind: PixelType,
+        pixels: Option<&Object>,
+    ) {
+        gl_call!(
+            &self.gl,
+            tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_array_buffer_view,
+            target as u32,
+            level as i32,
+            internal_format as i32,
+            width as i32,
+            height as i32,
+            0,
+            format as u32,
+            kind as u32,
+            pixels
+        )
+        .unwrap();
+    }
+
+    // web only: the source actually compiled by the driver, for example after ANGLE translated it to HLSL
+    pub fn get_translated_shader_source(&self, shader: &WebGLShader) -> Option<String> {
+        let ext = gl_call!(&self.gl, get_extension, "WEBGL_debug_shaders")
+            .ok()
+            .flatten()?;
+        let ext: web_sys::WebglDebugShaders = ext.unchecked_into();
+        let shader: web_sys::WebGlShader = self.get(shader.0).unwrap().into();
+        Some(ext.get_translated_shader_source(&shader)).filter(|source| !source.is_empty())
+    }
+
+    // web only: upload from a typed array the caller already owns, without copying it
+    pub fn tex_image2d_with_u8_array(
+        &self,
+        target: TextureBindPoint,
+        level: u8,
+        internal_format: PixelFormat,
+        width: u16,
+        height: u16,
+        format: PixelFormat,
+        kind: PixelType,
+        pixels: &js_sys::Uint8Array,
+        src_offset: u32,
+    ) {
+        match &self.gl {
+            WebContext::Gl2(gl) => gl
+                .tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_array_buffer_view_and_src_offset(
+                    target as u32,
+                    level as i32,
+                    internal_format as i32,
+                    width as i32,
+                    height as i32,
+                    0,
+                    format as u32,
+                    kind as u32,
+                    pixels,
+                    src_offset,
+                ),
+            WebContext::Gl(gl) => gl
+                .tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_array_buffer_view(
+                    target as u32,
+                    level as i32,
+                    internal_format as i32,
+                    width as i32,
+                    height as i32,
+                    0,
+                    format as u32,
+                    kind as u32,
+                    // no src_offset on WebGL 1.0, a sub array is a view on the same memory
+                    Some(&pixels.subarray(src_offset, pixels.length())),
+                ),
+        }
+        .unwrap();
+    }
+
+    // WebGL 1.0 rejects the WebGL 2.0 storage modes with an invalid enum error
+    fn is_pixel_storage_supported(&self, storage: PixelStorageMode, context: &str) -> bool {
+        match storage {
+            PixelStorageMode::UnpackRowLength
+            | PixelStorageMode::UnpackSkipRows
+            | PixelStorageMode::UnpackSkipPixels => self.webgl2(context).is_some(),
+            _ => true,
         }
     }
 
     pub fn pixel_storei(&self, storage: PixelStorageMode, value: i32) {
-        gl_call!(&self.gl, pixel_storei, storage as u32, value);
+        if self.is_pixel_storage_supported(storage, "pixel_storei") {
+            gl_call!(&self.gl, pixel_storei, storage as u32, value);
+        }
+    }
+
+    pub fn get_pixel_storei(&self, storage: PixelStorageMode) -> i32 {
+        if !self.is_pixel_storage_supported(storage, "get_pixel_storei") {
+            return 0;
+        }
+        let val = gl_call!(&self.gl, get_parameter, storage as u32).unwrap();
+        // the WebGL specific flip and premultiply modes are booleans
+        match val.as_bool() {
+            Some(flag) => flag as i32,
+            None => val.as_f64().unwrap_or(0.0) as i32,
+        }
     }
 
     pub fn read_pixels(
@@ -721,6 +2048,31 @@ impl GLContext {
         .unwrap();
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub fn read_pixels_to_buffer(
+        &self,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+        format: PixelFormat,
+        kind: PixelType,
+        offset: u32,
+    ) {
+        if let Some(gl) = self.webgl2("read_pixels_to_buffer") {
+            gl.read_pixels_with_i32(
+                x as i32,
+                y as i32,
+                width as i32,
+                height as i32,
+                format as u32,
+                kind as u32,
+                offset as i32,
+            )
+            .unwrap();
+        }
+    }
+
     pub fn tex_sub_image2d(
         &self,
         target: TextureBindPoint,
@@ -749,6 +2101,81 @@ impl GLContext {
         .unwrap();
     }
 
+    // compressed formats are only available once the matching extension is enabled
+    fn compression_extensions(compression: TextureCompression) -> &'static [&'static str] {
+        match compression {
+            TextureCompression::RgbDxt1
+            | TextureCompression::RgbaDxt1
+            | TextureCompression::RgbaDxt3
+            | TextureCompression::RgbaDxt5 => &[
+                "WEBGL_compressed_texture_s3tc",
+                "MOZ_WEBGL_compressed_texture_s3tc",
+                "WEBKIT_WEBGL_compressed_texture_s3tc",
+            ],
+            TextureCompression::R11Eac
+            | TextureCompression::Rg11Eac
+            | TextureCompression::RgbEtc2
+            | TextureCompression::RgbPunchthroughAlpha1Etc2
+            | TextureCompression::RgbaEtc2Eac => &["WEBGL_compressed_texture_etc"],
+            TextureCompression::RgbaAstc4x4
+            | TextureCompression::RgbaAstc5x5
+            | TextureCompression::RgbaAstc6x6
+            | TextureCompression::RgbaAstc8x6
+            | TextureCompression::RgbaAstc8x8
+            | TextureCompression::RgbaAstc10x10
+            | TextureCompression::RgbaAstc12x12 => &["WEBGL_compressed_texture_astc"],
+        }
+    }
+
+    const ALL_COMPRESSION_EXTENSIONS: [&'static str; 6] = [
+        "WEBGL_compressed_texture_s3tc",
+        "MOZ_WEBGL_compressed_texture_s3tc",
+        "WEBKIT_WEBGL_compressed_texture_s3tc",
+        "WEBGL_compressed_texture_etc",
+        "WEBGL_compressed_texture_astc",
+        "EXT_texture_compression_bptc",
+    ];
+
+    fn enable_compression_extension(&self, compression: TextureCompression) -> bool {
+        Self::compression_extensions(compression)
+            .iter()
+            .any(|ext| self.get_extension(ext))
+    }
+
+    pub fn get_supported_compressed_formats(&self) -> Vec<u32> {
+        for ext in Self::ALL_COMPRESSION_EXTENSIONS.iter() {
+            self.get_extension(ext);
+        }
+        let formats = gl_call!(
+            &self.gl,
+            get_parameter,
+            Parameter::CompressedTextureFormats as u32
+        )
+        .unwrap();
+        js_sys::Uint32Array::from(formats).to_vec()
+    }
+
+    pub fn get_internalformat_samples(&self, internal_format: PixelFormat) -> Vec<i32> {
+        match &self.gl {
+            WebContext::Gl2(gl) => {
+                let samples = gl
+                    .get_internalformat_parameter(
+                        web_sys::WebGl2RenderingContext::RENDERBUFFER,
+                        internal_format as u32,
+                        web_sys::WebGl2RenderingContext::SAMPLES,
+                    )
+                    .unwrap();
+                if samples.is_null() {
+                    Vec::new()
+                } else {
+                    js_sys::Int32Array::from(samples).to_vec()
+                }
+            }
+            // no multisampled renderbuffers on WebGL 1.0
+            WebContext::Gl(_) => Vec::new(),
+        }
+    }
+
     pub fn compressed_tex_image2d(
         &self,
         target: TextureBindPoint,
@@ -758,10 +2185,17 @@ impl GLContext {
         height: u16,
         data: &[u8],
     ) {
-        // for some reason this needs to be called otherwise invalid format error, extension initialization?
-        let _ = self.get_extension("WEBGL_compressed_texture_s3tc")
-            || self.get_extension("MOZ_WEBGL_compressed_texture_s3tc")
-            || self.get_extension("WEBKIT_WEBGL_compressed_texture_s3tc");
+        // listing the formats enables their extensions, otherwise the format is rejected as invalid
+        if !self.is_compressed_format_listed(compression) {
+            self.log_at(
+                LogLevel::Error,
+                format!(
+                    "ERROR compressed_tex_image2d unsupported compressed texture format {:?}",
+                    compression
+                ),
+            );
+            return;
+        }
         gl_call!(
             &self.gl,
             compressed_tex_image_2d_with_u8_array,
@@ -774,6 +2208,43 @@ impl GLContext {
             data
         );
     }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn compressed_tex_sub_image2d(
+        &self,
+        target: TextureBindPoint,
+        level: u8,
+        xoffset: u16,
+        yoffset: u16,
+        width: u16,
+        height: u16,
+        compression: TextureCompression,
+        data: &[u8],
+    ) {
+        self.enable_compression_extension(compression);
+        // web-sys wants a mutable slice here
+        let mut data = data.to_vec();
+        gl_call!(
+            &self.gl,
+            compressed_tex_sub_image_2d_with_u8_array,
+            target as u32,
+            level as i32,
+            xoffset as i32,
+            yoffset as i32,
+            width as i32,
+            height as i32,
+            compression as u32,
+            &mut data
+        );
+    }
+
+    pub fn copy_image_sub_data(&self, _src: ImageRegion, _dst: ImageRegion, _size: [u32; 3]) {
+        // WebGL has no texture to texture copy, blit between framebuffers instead
+        self.log_at(
+            LogLevel::Error,
+            "ERROR copy_image_sub_data is not supported on WebGL",
+        );
+    }
     /*
        // pub fn get_active_uniform(&self, program: &WebGLProgram, location: u32) -> WebGLActiveInfo {
        //     let res = js! {
@@ -0,0 +1,2244 @@
+use glow::HasContext;
+use std::os::raw::c_void;
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::num::NonZeroU32;
+use std::rc::Rc;
+
+use crate::common::*;
+use crate::glenum::*;
+use crate::shared::{
+    check_pixels_len, debug_check_index_offset, same_uniform, uniform_components, PixelLayout,
+    StateCache, UniformLocations,
+};
+use crate::util::AsGlBytes;
+
+pub type Reference = u32;
+
+#[derive(Debug, Clone)]
+/// uni-gl internal OpenGL context, backed by a [`glow::Context`].
+///
+/// You shouldn't use this struct directly. Instead, call the methods on [`WebGLRenderingContext`]
+/// as it automatically dereferences into a [`GLContext`].
+pub struct GLContext {
+    gl: Rc<glow::Context>,
+    /// openGL internal reference
+    pub reference: Reference,
+    /// whether this context is a WebGL 2.0 context
+    pub is_webgl2: bool,
+    pub(crate) log_level: Cell<LogLevel>,
+    pub(crate) uniform_locations: UniformLocations,
+    pub(crate) state_cache: StateCache,
+    // glow can't query the type of a shader
+    shader_kinds: Rc<RefCell<HashMap<Reference, ShaderKind>>>,
+    default_vertex_array: Option<WebGLVertexArray>,
+}
+
+impl PartialEq for GLContext {
+    fn eq(&self, other: &GLContext) -> bool {
+        Rc::ptr_eq(&self.gl, &other.gl)
+    }
+}
+
+/// the glow object named by a uni-gl handle, None for 0 which names no object in OpenGL
+fn object<T>(reference: Reference, wrap: fn(NonZeroU32) -> T) -> Option<T> {
+    NonZeroU32::new(reference).map(wrap)
+}
+
+pub type WebGLContext<'p> = Box<dyn 'p + for<'a> FnMut(&'a str) -> *const c_void>;
+
+impl WebGLRenderingContext {
+    /// create an OpenGL context.
+    ///
+    /// uni-gl should be used with the uni-app crate.
+    /// You can create a [`WebGLRenderingContext`] with following code :
+    /// ```ignore
+    /// let app = uni_app::App::new(...);
+    /// let gl = uni_gl::WebGLRenderingContext::new(app.canvas());
+    /// ```
+    pub fn new<'p>(mut loadfn: WebGLContext<'p>) -> WebGLRenderingContext {
+        let gl = unsafe { glow::Context::from_loader_function(move |name| loadfn(name)) };
+        WebGLRenderingContext::from_glow(Rc::new(gl))
+    }
+
+    /// create an OpenGL context that several owners can keep, see [`WebGLRenderingContext::new`].
+    ///
+    /// The context caches state in `Cell`s and `RefCell`s, so it can be shared
+    /// but must stay on the thread owning the OpenGL context.
+    pub fn new_shared<'p>(loadfn: WebGLContext<'p>) -> Rc<WebGLRenderingContext> {
+        Rc::new(WebGLRenderingContext::new(loadfn))
+    }
+
+    /// create an OpenGL context, see [`WebGLRenderingContext::new`], with its viewport
+    /// set to cover a window of the given size.
+    ///
+    /// On the web, the viewport is set from the size of the canvas.
+    pub fn new_with_size<'p>(
+        loadfn: WebGLContext<'p>,
+        width: u32,
+        height: u32,
+    ) -> WebGLRenderingContext {
+        let context = WebGLRenderingContext::new(loadfn);
+        context.viewport(0, 0, width, height);
+        context
+    }
+
+    /// wrap an existing glow context, for applications that already load OpenGL through glow.
+    ///
+    /// The application keeps its own reference to the context, see [`GLContext::glow`].
+    pub fn from_glow(gl: Rc<glow::Context>) -> WebGLRenderingContext {
+        WebGLRenderingContext {
+            common: GLContext::from_glow(gl),
+        }
+    }
+}
+
+impl GLContext {
+    // unlike the gl backend, there is no global OpenGL loader to build a context from,
+    // so the only public constructors are on WebGLRenderingContext
+    pub(crate) fn from_glow(gl: Rc<glow::Context>) -> GLContext {
+        // core profiles have no vertex array bound by default, where WebGL has an implicit one.
+        // Vertex array objects need OpenGL (ES) 3.0
+        let default_vertex_array = if gl.version().major >= 3 {
+            unsafe { gl.create_vertex_array() }.ok().map(|vao| {
+                unsafe {
+                    gl.bind_vertex_array(Some(vao));
+                }
+                WebGLVertexArray(vao.0.get())
+            })
+        } else {
+            None
+        };
+        let context = GLContext {
+            gl,
+            reference: 0,
+            is_webgl2: true,
+            log_level: Cell::new(LogLevel::default()),
+            uniform_locations: RefCell::new(HashMap::new()),
+            state_cache: StateCache::default(),
+            shader_kinds: Rc::new(RefCell::new(HashMap::new())),
+            default_vertex_array,
+        };
+        if context.log_enabled(LogLevel::Info) {
+            context.print_gl_info();
+        }
+        context
+    }
+
+    /// the underlying glow context
+    pub fn glow(&self) -> &Rc<glow::Context> {
+        &self.gl
+    }
+
+    /// return the vendor, renderer and version strings of the implementation
+    pub fn gpu_info(&self) -> GpuInfo {
+        unsafe {
+            GpuInfo {
+                vendor: self.gl.get_parameter_string(glow::VENDOR),
+                renderer: self.gl.get_parameter_string(glow::RENDERER),
+                version: self.gl.get_parameter_string(glow::VERSION),
+                shading_language_version: self
+                    .gl
+                    .get_parameter_string(glow::SHADING_LANGUAGE_VERSION),
+            }
+        }
+    }
+
+    fn get_float_range(&self, param: u32) -> (f32, f32) {
+        let mut res = [0.0f32; 2];
+        unsafe {
+            self.gl.get_parameter_f32_slice(param, &mut res);
+        }
+        (res[0], res[1])
+    }
+
+    fn get_rect(&self, param: u32) -> Rect {
+        let mut res = [0; 4];
+        unsafe {
+            self.gl.get_parameter_i32_slice(param, &mut res);
+        }
+        Rect {
+            x: res[0],
+            y: res[1],
+            w: res[2] as u32,
+            h: res[3] as u32,
+        }
+    }
+
+    /// the current viewport
+    pub fn get_viewport(&self) -> Rect {
+        self.get_rect(glow::VIEWPORT)
+    }
+
+    /// the current scissor box
+    pub fn get_scissor_box(&self) -> Rect {
+        self.get_rect(glow::SCISSOR_BOX)
+    }
+
+    /// the minimum and maximum widths of aliased lines
+    pub fn line_width_range(&self) -> (f32, f32) {
+        self.get_float_range(glow::ALIASED_LINE_WIDTH_RANGE)
+    }
+
+    /// the minimum and maximum sizes of points
+    pub fn point_size_range(&self) -> (f32, f32) {
+        // ALIASED_POINT_SIZE_RANGE was removed from the desktop core profile
+        if self.gl.version().is_embedded {
+            self.get_float_range(glow::ALIASED_POINT_SIZE_RANGE)
+        } else {
+            self.get_float_range(glow::POINT_SIZE_RANGE)
+        }
+    }
+
+    /// set the width of rasterized lines, clamped to [`GLContext::line_width_range`]
+    pub fn line_width(&self, width: f32) {
+        unsafe {
+            self.gl.line_width(width);
+        }
+        self.check_gl_error("line_width");
+    }
+
+    /// the (major, minor) OpenGL version of this context
+    pub fn gl_version(&self) -> (u32, u32) {
+        let version = self.gl.version();
+        (version.major, version.minor)
+    }
+
+    /// whether this context uses the core profile, where deprecated features are removed
+    pub fn is_core_profile(&self) -> bool {
+        // OpenGL ES and WebGL have no profiles
+        if self.gl.version().is_embedded {
+            return false;
+        }
+        let mask = unsafe { self.gl.get_parameter_i32(glow::CONTEXT_PROFILE_MASK) } as u32;
+        mask & glow::CONTEXT_CORE_PROFILE_BIT != 0
+    }
+
+    /// the vertex array object bound when no other one is, like WebGL's implicit one.
+    ///
+    /// It is created with the context on OpenGL (ES) 3.0+ and must not be deleted.
+    /// None on older versions and on the web, where vertex arrays are optional
+    pub fn default_vertex_array(&self) -> Option<&WebGLVertexArray> {
+        self.default_vertex_array.as_ref()
+    }
+
+    /// whether an optional capability is available with the OpenGL version of this context
+    pub fn supports(&self, feature: Feature) -> bool {
+        let version = self.gl.version();
+        let required = if version.is_embedded {
+            feature.gles_version()
+        } else {
+            feature.gl_version()
+        };
+        (version.major, version.minor) >= required
+    }
+
+    /// print a message on the standard output
+    pub fn print<T: Into<String>>(msg: T) {
+        print!("{}", msg.into());
+    }
+
+    /// panics with a proper message if the last OpenGL call returned an error
+    fn check_gl_error(&self, msg: &str) {
+        let err = unsafe { self.gl.get_error() };
+        if err != glow::NO_ERROR {
+            panic!(
+                "GLError: {} {} ({})",
+                msg,
+                err,
+                match err {
+                    glow::INVALID_ENUM => "invalid enum",
+                    glow::INVALID_OPERATION => "invalid operation",
+                    glow::INVALID_VALUE => "invalid value",
+                    glow::OUT_OF_MEMORY => "out of memory",
+                    glow::STACK_OVERFLOW => "stack overflow",
+                    glow::STACK_UNDERFLOW => "stack underflow",
+                    _ => "unknown error",
+                }
+            );
+        }
+    }
+
+    /// the glow object of a handle passed where OpenGL needs an object, logging an error for 0
+    fn required<T>(
+        &self,
+        context: &str,
+        reference: Reference,
+        wrap: fn(NonZeroU32) -> T,
+    ) -> Option<T> {
+        let object = object(reference, wrap);
+        if object.is_none() {
+            self.log_at(
+                LogLevel::Error,
+                format!("ERROR {} : the 0 handle names no object", context),
+            );
+        }
+        object
+    }
+
+    pub(crate) fn get_error(&self) -> u32 {
+        unsafe { self.gl.get_error() }
+    }
+
+    /// create a new OpenGL buffer
+    pub fn create_buffer(&self) -> WebGLBuffer {
+        let buffer = unsafe { self.gl.create_buffer() }.unwrap();
+        self.check_gl_error("create_buffer");
+        WebGLBuffer(buffer.0.get())
+    }
+
+    /// delete an existing buffer
+    pub fn delete_buffer(&self, buffer: &WebGLBuffer) {
+        unsafe {
+            if let Some(buffer) = object(buffer.0, glow::NativeBuffer) {
+                self.gl.delete_buffer(buffer);
+            }
+        }
+        self.check_gl_error("delete_buffer");
+    }
+
+    /// bind a buffer to current state.
+    pub fn bind_buffer(&self, kind: BufferKind, buffer: &WebGLBuffer) {
+        unsafe {
+            self.gl
+                .bind_buffer(kind as _, object(buffer.0, glow::NativeBuffer));
+        }
+        self.check_gl_error("bind_buffer");
+    }
+
+    /// bind an index buffer, recorded in the bound vertex array.
+    ///
+    /// Prefer it to `bind_buffer(BufferKind::ElementArray, ..)`: on WebGL 1.0, which has no vertex array,
+    /// the web backend remembers the index buffer of each vertex array and binds it again in `bind_vertex_array`
+    pub fn bind_element_buffer(&self, buffer: &WebGLBuffer) {
+        self.bind_buffer(BufferKind::ElementArray, buffer);
+    }
+
+    /// bind a buffer to an indexed binding point of a uniform or transform feedback buffer
+    pub fn bind_buffer_base(&self, kind: BufferKind, index: u32, buffer: &WebGLBuffer) {
+        unsafe {
+            self.gl
+                .bind_buffer_base(kind as _, index, object(buffer.0, glow::NativeBuffer));
+        }
+        self.check_gl_error("bind_buffer_base");
+    }
+
+    /// bind a range of a buffer to an indexed binding point of a uniform or transform feedback buffer
+    pub fn bind_buffer_range(
+        &self,
+        kind: BufferKind,
+        index: u32,
+        buffer: &WebGLBuffer,
+        offset: usize,
+        size: usize,
+    ) {
+        unsafe {
+            self.gl.bind_buffer_range(
+                kind as _,
+                index,
+                object(buffer.0, glow::NativeBuffer),
+                offset as _,
+                size as _,
+            );
+        }
+        self.check_gl_error("bind_buffer_range");
+    }
+
+    /// return the buffer, offset or size bound to an indexed binding point
+    pub fn get_indexed_parameter_i32(
+        &self,
+        target: BufferKind,
+        index: u32,
+        pname: IndexedParam,
+    ) -> i32 {
+        let pname = match self.indexed_pname(target, pname) {
+            Some(pname) => pname,
+            None => return 0,
+        };
+        let value = unsafe { self.gl.get_parameter_indexed_i32(pname, index) };
+        self.check_gl_error("get_indexed_parameter_i32");
+        value
+    }
+
+    /// fills a buffer with data : bytes, or a slice of numbers or [`Pod`] values.
+    ///
+    /// kind : see [`GLContext::bind_buffer`].
+    pub fn buffer_data(&self, kind: BufferKind, data: impl AsGlBytes, draw: DrawMode) {
+        let data = data.as_gl_bytes();
+        unsafe {
+            self.gl.buffer_data_u8_slice(kind as _, data, draw as _);
+        }
+        self.check_gl_error("buffer_data");
+    }
+
+    /// allocate uninitialized storage for a buffer, to be filled later with [`GLContext::buffer_sub_data`].
+    ///
+    /// kind : see [`GLContext::bind_buffer`].
+    pub fn buffer_data_size(&self, kind: BufferKind, size: usize, draw: DrawMode) {
+        unsafe {
+            self.gl.buffer_data_size(kind as _, size as _, draw as _);
+        }
+        self.check_gl_error("buffer_data_size");
+    }
+
+    /// update a subset of a buffer
+    ///
+    /// kind : see [`GLContext::bind_buffer`].
+    ///
+    /// offset : offset in the buffer where data replacement will begin
+    pub fn buffer_sub_data(&self, kind: BufferKind, offset: u32, data: impl AsGlBytes) {
+        let data = data.as_gl_bytes();
+        unsafe {
+            self.gl
+                .buffer_sub_data_u8_slice(kind as _, offset as _, data);
+        }
+        self.check_gl_error("buffer_sub_data");
+    }
+
+    /// return information about the buffer currently bound to kind (size or usage hint)
+    pub fn get_buffer_parameter_i32(&self, kind: BufferKind, pname: BufferParameter) -> i32 {
+        let res = unsafe { self.gl.get_buffer_parameter_i32(kind as _, pname as _) };
+        self.check_gl_error("get_buffer_parameter_i32");
+        res
+    }
+
+    /// create an immutable storage of size bytes for the buffer bound to kind.
+    ///
+    /// flags is a combination of [`BufferAccessBit`] values. Requires OpenGL 4.4
+    pub fn buffer_storage(&self, kind: BufferKind, size: usize, flags: u32) {
+        unsafe {
+            self.gl.buffer_storage(kind as _, size as _, None, flags);
+        }
+        self.check_gl_error("buffer_storage");
+    }
+
+    /// map a range of the buffer bound to kind into client memory.
+    ///
+    /// access is a combination of [`BufferAccessBit`] values.
+    /// The pointer is valid for length bytes until [`GLContext::unmap_buffer`] is called
+    pub fn map_buffer_range(
+        &self,
+        kind: BufferKind,
+        offset: usize,
+        length: usize,
+        access: u32,
+    ) -> *mut u8 {
+        let res = unsafe {
+            self.gl
+                .map_buffer_range(kind as _, offset as _, length as _, access)
+        };
+        self.check_gl_error("map_buffer_range");
+        res
+    }
+
+    /// release the mapping of the buffer bound to kind
+    pub fn unmap_buffer(&self, kind: BufferKind) {
+        unsafe {
+            self.gl.unmap_buffer(kind as _);
+        }
+        self.check_gl_error("unmap_buffer");
+    }
+
+    /// this buffer is not bound to the current state anymore.
+    pub fn unbind_buffer(&self, kind: BufferKind) {
+        unsafe {
+            self.gl.bind_buffer(kind as _, None);
+        }
+        self.check_gl_error("unbind_buffer");
+    }
+
+    /// create a new shader.
+    pub fn create_shader(&self, kind: ShaderKind) -> WebGLShader {
+        let shader = unsafe { self.gl.create_shader(kind as _) }.unwrap();
+        self.check_gl_error("create_shader");
+        self.shader_kinds.borrow_mut().insert(shader.0.get(), kind);
+        WebGLShader(shader.0.get())
+    }
+
+    /// load precompiled binaries into shaders, for example SPIR-V modules
+    /// (format `GL_SHADER_BINARY_FORMAT_SPIR_V` = 0x9551, OpenGL 4.6).
+    ///
+    /// SPIR-V shaders must then be specialized with [`GLContext::specialize_shader`] instead of compiled
+    ///
+    /// Not supported with the glow backend, which logs an error instead
+    pub fn shader_binary(&self, _shaders: &[&WebGLShader], _format: u32, _binary: &[u8]) {
+        // glow does not expose glShaderBinary
+        self.log_at(
+            LogLevel::Error,
+            "ERROR shader_binary is not supported with the glow backend",
+        );
+    }
+
+    /// set the entry point and the specialization constants (index, value) of a SPIR-V shader.
+    /// Takes the place of [`GLContext::compile_shader`] for shaders loaded with [`GLContext::shader_binary`]
+    ///
+    /// Not supported with the glow backend, which logs an error instead
+    pub fn specialize_shader(
+        &self,
+        _shader: &WebGLShader,
+        _entry_point: &str,
+        _constants: &[(u32, u32)],
+    ) {
+        self.log_at(
+            LogLevel::Error,
+            "ERROR specialize_shader is not supported with the glow backend",
+        );
+    }
+
+    /// return informations about a shader
+    ///
+    /// The glow backend only answers [`ShaderParameter::CompileStatus`] and [`ShaderParameter::ShaderType`]
+    pub fn get_shader_parameter(&self, shader: &WebGLShader, pname: ShaderParameter) -> i32 {
+        match pname {
+            ShaderParameter::CompileStatus => self
+                .required("get_shader_parameter", shader.0, glow::NativeShader)
+                .map_or(0, |shader| unsafe {
+                    self.gl.get_shader_compile_status(shader) as i32
+                }),
+            ShaderParameter::ShaderType => self
+                .shader_kinds
+                .borrow()
+                .get(&shader.0)
+                .map_or(0, |kind| *kind as i32),
+            _ => {
+                self.log_at(
+                    LogLevel::Error,
+                    format!(
+                        "ERROR get_shader_parameter {:?} is not supported with the glow backend",
+                        pname
+                    ),
+                );
+                0
+            }
+        }
+    }
+
+    /// set or replace the source code in a shader
+    pub fn shader_source(&self, shader: &WebGLShader, source: &str) {
+        if let Some(shader) = self.required("shader_source", shader.0, glow::NativeShader) {
+            unsafe {
+                self.gl.shader_source(shader, source);
+            }
+            self.check_gl_error("shader_source");
+        }
+    }
+
+    /// set or replace the source code in a shader from several strings.
+    ///
+    /// The strings are concatenated by OpenGL, which keeps `#line` directives meaningful
+    pub fn shader_source_multi(&self, shader: &WebGLShader, sources: &[&str]) {
+        // glow only accepts one string, OpenGL would concatenate them the same way
+        self.shader_source(shader, &sources.concat());
+    }
+
+    /// compile a shader
+    pub fn compile_shader(&self, shader: &WebGLShader) {
+        if let Err(log) = self.try_compile_shader(shader) {
+            panic!("{}", log);
+        }
+    }
+
+    pub(crate) fn try_compile_shader(&self, shader: &WebGLShader) -> Result<(), String> {
+        let shader = object(shader.0, glow::NativeShader)
+            .ok_or_else(|| "the 0 handle names no shader".to_string())?;
+        unsafe {
+            self.gl.compile_shader(shader);
+            if !self.gl.get_shader_compile_status(shader) {
+                return Err(self.gl.get_shader_info_log(shader));
+            }
+        }
+        self.check_gl_error("compile_shader");
+        Ok(())
+    }
+
+    /// delete a shader. It is only destroyed once detached from every program
+    pub fn delete_shader(&self, shader: &WebGLShader) {
+        unsafe {
+            if let Some(shader) = object(shader.0, glow::NativeShader) {
+                self.gl.delete_shader(shader);
+            }
+        }
+        self.check_gl_error("delete_shader");
+    }
+
+    /// create a program
+    pub fn create_program(&self) -> WebGLProgram {
+        let program = unsafe { self.gl.create_program() }.unwrap();
+        self.check_gl_error("create_program");
+        WebGLProgram(program.0.get())
+    }
+
+    /// link a program
+    pub fn link_program(&self, program: &WebGLProgram) {
+        if let Err(log) = self.try_link_program(program) {
+            panic!("{}", log);
+        }
+    }
+
+    pub(crate) fn try_link_program(&self, program: &WebGLProgram) -> Result<(), String> {
+        self.forget_uniform_locations(program);
+        let program = object(program.0, glow::NativeProgram)
+            .ok_or_else(|| "the 0 handle names no program".to_string())?;
+        unsafe {
+            self.gl.link_program(program);
+            if !self.gl.get_program_link_status(program) {
+                return Err(self.gl.get_program_info_log(program));
+            }
+        }
+        self.check_gl_error("link_program");
+        Ok(())
+    }
+
+    /// delete a program. It is only destroyed once it is no longer in use
+    pub fn delete_program(&self, program: &WebGLProgram) {
+        self.forget_uniform_locations(program);
+        unsafe {
+            if let Some(program) = object(program.0, glow::NativeProgram) {
+                self.gl.delete_program(program);
+            }
+        }
+        self.check_gl_error("delete_program");
+    }
+
+    /// bind a program to the current state.
+    pub fn use_program(&self, program: &WebGLProgram) {
+        unsafe {
+            self.gl.use_program(object(program.0, glow::NativeProgram));
+        }
+        self.check_gl_error("use_program");
+    }
+
+    pub(crate) fn is_program_in_use(&self) -> bool {
+        unsafe { self.gl.get_parameter_i32(glow::CURRENT_PROGRAM) != 0 }
+    }
+
+    /// attach a shader to a program. A program must have two shaders : vertex and fragment shader.
+    pub fn attach_shader(&self, program: &WebGLProgram, shader: &WebGLShader) {
+        let program = self.required("attach_shader", program.0, glow::NativeProgram);
+        let shader = self.required("attach_shader", shader.0, glow::NativeShader);
+        if let (Some(program), Some(shader)) = (program, shader) {
+            unsafe {
+                self.gl.attach_shader(program, shader);
+            }
+            self.check_gl_error("attach_shader");
+        }
+    }
+
+    /// detach a shader from a program
+    pub fn detach_shader(&self, program: &WebGLProgram, shader: &WebGLShader) {
+        let program = self.required("detach_shader", program.0, glow::NativeProgram);
+        let shader = self.required("detach_shader", shader.0, glow::NativeShader);
+        if let (Some(program), Some(shader)) = (program, shader) {
+            unsafe {
+                self.gl.detach_shader(program, shader);
+            }
+            self.check_gl_error("detach_shader");
+        }
+    }
+
+    /// return the shaders attached to a program
+    ///
+    /// Not supported with the glow backend, which logs an error instead
+    pub fn get_attached_shaders(&self, _program: &WebGLProgram) -> Vec<WebGLShader> {
+        // glow does not expose glGetAttachedShaders
+        self.log_at(
+            LogLevel::Error,
+            "ERROR get_attached_shaders is not supported with the glow backend",
+        );
+        Vec::new()
+    }
+
+    /// associate a generic vertex attribute index with a named attribute
+    pub fn bind_attrib_location(&self, program: &WebGLProgram, name: &str, loc: u32) {
+        let c_name = self.strip_nul(name, "bind_attrib_location");
+        if let Some(program) = self.required("bind_attrib_location", program.0, glow::NativeProgram)
+        {
+            unsafe {
+                self.gl.bind_attrib_location(program, loc, &c_name);
+            }
+            self.check_gl_error("bind_attrib_location");
+        }
+    }
+
+    /// bind a user-defined fragment shader output variable to a color number (draw buffer)
+    ///
+    /// must be called before [`GLContext::link_program`] to take effect.
+    pub fn bind_frag_data_location(&self, program: &WebGLProgram, color_number: u32, name: &str) {
+        let c_name = self.strip_nul(name, "bind_frag_data_location");
+        if let Some(program) =
+            self.required("bind_frag_data_location", program.0, glow::NativeProgram)
+        {
+            unsafe {
+                self.gl
+                    .bind_frag_data_location(program, color_number, &c_name);
+            }
+            self.check_gl_error("bind_frag_data_location");
+        }
+    }
+
+    /// return the color number a fragment shader output variable is bound to
+    pub fn get_frag_data_location(&self, program: &WebGLProgram, name: &str) -> Option<i32> {
+        let c_name = self.strip_nul(name, "get_frag_data_location");
+        let program = self.required("get_frag_data_location", program.0, glow::NativeProgram)?;
+        let location = unsafe { self.gl.get_frag_data_location(program, &c_name) };
+        self.check_gl_error("get_frag_data_location");
+        if location == -1 {
+            None
+        } else {
+            Some(location)
+        }
+    }
+
+    /// return the location of an attribute variable
+    pub fn get_attrib_location(&self, program: &WebGLProgram, name: &str) -> Option<u32> {
+        let c_name = self.strip_nul(name, "get_attrib_location");
+        let program = self.required("get_attrib_location", program.0, glow::NativeProgram)?;
+        let location = unsafe { self.gl.get_attrib_location(program, &c_name) };
+        self.check_gl_error("get_attrib_location");
+        location
+    }
+
+    /// return the location of a uniform variable
+    pub fn get_uniform_location(
+        &self,
+        program: &WebGLProgram,
+        name: &str,
+    ) -> Option<WebGLUniformLocation> {
+        let c_name = self.strip_nul(name, "get_uniform_location");
+        let program = self.required("get_uniform_location", program.0, glow::NativeProgram)?;
+        let location = unsafe { self.gl.get_uniform_location(program, &c_name) };
+        self.check_gl_error(&format!("get_uniform_location {}", name));
+        location.map(|location| WebGLUniformLocation {
+            reference: location.0,
+            name: name.into(),
+        })
+    }
+
+    fn uniform_components(&self, program: &WebGLProgram, name: &str) -> usize {
+        let program = match object(program.0, glow::NativeProgram) {
+            Some(program) => program,
+            None => return 1,
+        };
+        let count = unsafe { self.gl.get_active_uniforms(program) };
+        (0..count)
+            .filter_map(|index| unsafe { self.gl.get_active_uniform(program, index) })
+            .find(|uniform| same_uniform(&uniform.name, name))
+            .map_or(1, |uniform| uniform_components(uniform.utype))
+    }
+
+    /// return the value of a float uniform (one value per component)
+    pub fn get_uniform_f32(
+        &self,
+        program: &WebGLProgram,
+        location: &WebGLUniformLocation,
+    ) -> Vec<f32> {
+        let mut res = vec![0.0; self.uniform_components(program, &location.name)];
+        if let Some(program) = self.required("get_uniform_f32", program.0, glow::NativeProgram) {
+            unsafe {
+                self.gl.get_uniform_f32(
+                    program,
+                    &glow::NativeUniformLocation(location.reference),
+                    &mut res,
+                );
+            }
+            self.check_gl_error("get_uniform_f32");
+        }
+        res
+    }
+
+    /// return the value of an integer, boolean or sampler uniform (one value per component)
+    pub fn get_uniform_i32(
+        &self,
+        program: &WebGLProgram,
+        location: &WebGLUniformLocation,
+    ) -> Vec<i32> {
+        let mut res = vec![0; self.uniform_components(program, &location.name)];
+        if let Some(program) = self.required("get_uniform_i32", program.0, glow::NativeProgram) {
+            unsafe {
+                self.gl.get_uniform_i32(
+                    program,
+                    &glow::NativeUniformLocation(location.reference),
+                    &mut res,
+                );
+            }
+            self.check_gl_error("get_uniform_i32");
+        }
+        res
+    }
+
+    /// define an array of generic vertex attribute data
+    pub fn vertex_attrib_pointer(
+        &self,
+        location: u32,
+        size: AttributeSize,
+        kind: DataType,
+        normalized: bool,
+        stride: u32,
+        offset: u32,
+    ) {
+        unsafe {
+            self.gl.vertex_attrib_pointer_f32(
+                location,
+                size as _,
+                kind as _,
+                normalized,
+                stride as _,
+                offset as _,
+            );
+        }
+        self.check_gl_error("vertex_attrib_pointer");
+    }
+
+    /// enable a generic vertex attribute array
+    pub fn enable_vertex_attrib_array(&self, location: u32) {
+        unsafe {
+            self.gl.enable_vertex_attrib_array(location);
+        }
+        self.check_gl_error("enable_vertex_attrib_array");
+    }
+
+    /// specify clear values for the color buffers. The call is skipped if the color did not change,
+    /// see [`GLContext::flush_state_cache`]
+    pub fn clear_color(&self, r: f32, g: f32, b: f32, a: f32) {
+        if !self.update_clear_color_cache([r, g, b, a]) {
+            return;
+        }
+        unsafe {
+            self.gl.clear_color(r, g, b, a);
+        }
+        self.check_gl_error("clear_color");
+    }
+
+    /// enable GL capabilities.
+    ///
+    /// flag should be one of [`Flag`]. The call is skipped if uni-gl already enabled it,
+    /// see [`GLContext::flush_state_cache`]
+    pub fn enable(&self, flag: i32) {
+        if !self.update_flag_cache(flag, true) {
+            return;
+        }
+        unsafe {
+            self.gl.enable(flag as _);
+        }
+        self.check_gl_error("enable");
+    }
+
+    /// disable GL capabilities.
+    ///
+    /// flag should be one of [`Flag`]. The call is skipped if uni-gl already disabled it
+    pub fn disable(&self, flag: i32) {
+        if !self.update_flag_cache(flag, false) {
+            return;
+        }
+        unsafe {
+            self.gl.disable(flag as _);
+        }
+        self.check_gl_error("disable");
+    }
+
+    /// test whether a capability is enabled
+    pub fn is_enabled(&self, flag: Flag) -> bool {
+        let res = unsafe { self.gl.is_enabled(flag as _) };
+        self.check_gl_error("is_enabled");
+        res
+    }
+
+    /// specify whether front- or back-facing polygons can be culled
+    pub fn cull_face(&self, flag: Culling) {
+        unsafe {
+            self.gl.cull_face(flag as _);
+        }
+        self.check_gl_error("cullface");
+    }
+
+    /// enable or disable writing into the depth buffer
+    pub fn depth_mask(&self, b: bool) {
+        unsafe {
+            self.gl.depth_mask(b);
+        }
+        self.check_gl_error("depth_mask");
+    }
+
+    /// enable or disable writing of the color components into the color buffer
+    pub fn color_mask(&self, r: bool, g: bool, b: bool, a: bool) {
+        unsafe {
+            self.gl.color_mask(r, g, b, a);
+        }
+        self.check_gl_error("color_mask");
+    }
+
+    /// specify the value used for depth buffer comparisons
+    pub fn depth_func(&self, d: CompareFunc) {
+        unsafe {
+            self.gl.depth_func(d as _);
+        }
+        self.check_gl_error("depth_func");
+    }
+
+    /// return the current depth comparison function, or its raw value if uni-gl has no matching variant
+    pub fn get_depth_func(&self) -> Result<CompareFunc, u32> {
+        let func = unsafe { self.gl.get_parameter_i32(Parameter::DepthFunc as _) };
+        self.check_gl_error("get_depth_func");
+        CompareFunc::try_from(func as u32)
+    }
+
+    /// return the current source and destination RGB blend factors, or the raw value of one
+    /// uni-gl has no variant for, like a factor set by other code
+    pub fn get_blend_func(&self) -> Result<(BlendMode, BlendMode), u32> {
+        let (src, dst) = unsafe {
+            (
+                self.gl.get_parameter_i32(Parameter::BlendSrcRgb as _),
+                self.gl.get_parameter_i32(Parameter::BlendDstRgb as _),
+            )
+        };
+        self.check_gl_error("get_blend_func");
+        Ok((
+            BlendMode::try_from(src as u32)?,
+            BlendMode::try_from(dst as u32)?,
+        ))
+    }
+
+    /// specify the clear value for the depth buffer
+    pub fn clear_depth(&self, value: f32) {
+        unsafe {
+            self.gl.clear_depth(value as _);
+        }
+        self.check_gl_error("clear_depth");
+    }
+
+    /// specify the clear value for the depth buffer, in double precision.
+    ///
+    /// The web backend truncates the value to f32 as WebGL only supports single precision.
+    pub fn clear_depth_f64(&self, value: f64) {
+        unsafe {
+            self.gl.clear_depth_f64(value);
+        }
+        self.check_gl_error("clear_depth_f64");
+    }
+
+    /// specify mapping of depth values from normalized device coordinates to window coordinates
+    pub fn depth_range(&self, near: f32, far: f32) {
+        unsafe {
+            self.gl.depth_range_f32(near, far);
+        }
+        self.check_gl_error("depth_range");
+    }
+
+    /// same as [`GLContext::depth_range`], in double precision.
+    ///
+    /// The web backend truncates the values to f32 as WebGL only supports single precision.
+    pub fn depth_range_f64(&self, near: f64, far: f64) {
+        unsafe {
+            self.gl.depth_range_f64(near, far);
+        }
+        self.check_gl_error("depth_range_f64");
+    }
+
+    /// clear buffers to preset values
+    pub fn clear(&self, bit: BufferBit) {
+        unsafe {
+            self.gl.clear(bit as _);
+        }
+        self.check_gl_error("clear");
+    }
+
+    /// set the viewport
+    pub fn viewport(&self, x: i32, y: i32, width: u32, height: u32) {
+        unsafe {
+            self.gl.viewport(x, y, width as _, height as _);
+        }
+        self.check_gl_error("viewport");
+    }
+
+    /// set the viewport of one of the viewports used by layered rendering (OpenGL 4.1)
+    pub fn viewport_indexed(&self, index: u32, rect: Rect) {
+        self.viewport_slice(index, &[rect]);
+        self.check_gl_error("viewport_indexed");
+    }
+
+    /// set the viewports used by layered rendering, starting from the first one (OpenGL 4.1)
+    pub fn viewport_array(&self, rects: &[Rect]) {
+        self.viewport_slice(0, rects);
+        self.check_gl_error("viewport_array");
+    }
+
+    fn viewport_slice(&self, first: u32, rects: &[Rect]) {
+        let values: Vec<[f32; 4]> = rects
+            .iter()
+            .map(|r| [r.x as f32, r.y as f32, r.w as f32, r.h as f32])
+            .collect();
+        unsafe {
+            self.gl
+                .viewport_f32_slice(first, values.len() as _, &values);
+        }
+    }
+
+    /// to call when the window is resized. Sets the viewport to the whole window
+    pub fn resize(&self, width: u32, height: u32) {
+        self.viewport(0, 0, width, height);
+    }
+
+    /// set the number of vertices in each patch drawn with [`Primitives::Patches`]
+    pub fn patch_parameter_vertices(&self, n: i32) {
+        unsafe {
+            self.gl.patch_parameter_i32(glow::PATCH_VERTICES, n);
+        }
+        self.check_gl_error("patch_parameter_vertices");
+    }
+
+    /// set the index restarting primitives when [`Flag::PrimitiveRestart`] is enabled
+    ///
+    /// Not supported with the glow backend, which logs an error instead
+    pub fn primitive_restart_index(&self, _index: u32) {
+        // glow does not expose glPrimitiveRestartIndex, use Flag::PrimitiveRestartFixedIndex
+        self.log_at(
+            LogLevel::Error,
+            "ERROR primitive_restart_index is not supported with the glow backend",
+        );
+    }
+
+    /// set the minimum fraction of samples shaded independently when [`Flag::SampleShading`] is enabled
+    ///
+    /// Not supported with the glow backend, which logs an error instead
+    pub fn min_sample_shading(&self, _value: f32) {
+        // glow does not expose glMinSampleShading
+        self.log_at(
+            LogLevel::Error,
+            "ERROR min_sample_shading is not supported with the glow backend",
+        );
+    }
+
+    /// whether read_pixels clamps the colors to [0, 1]. Disable it to read back float framebuffers
+    ///
+    /// Not supported with the glow backend, which logs an error instead
+    pub fn clamp_read_color(&self, _clamp: bool) {
+        // glow does not expose glClampColor
+        self.log_at(
+            LogLevel::Error,
+            "ERROR clamp_read_color is not supported with the glow backend",
+        );
+    }
+
+    /// set the depth range outside of which fragments are discarded when [`Flag::DepthBoundsTest`] is enabled.
+    /// Requires GL_EXT_depth_bounds_test
+    ///
+    /// Not supported with the glow backend, which logs an error instead
+    pub fn depth_bounds(&self, _min: f32, _max: f32) {
+        // glow does not expose glDepthBoundsEXT
+        self.log_at(
+            LogLevel::Error,
+            "ERROR depth_bounds is not supported with the glow backend",
+        );
+    }
+
+    /// set a word of the sample mask applied when [`Flag::SampleMask`] is enabled
+    ///
+    /// Not supported with the glow backend, which logs an error instead
+    pub fn sample_mask(&self, _index: u32, _mask: u32) {
+        // glow does not expose glSampleMaski
+        self.log_at(
+            LogLevel::Error,
+            "ERROR sample_mask is not supported with the glow backend",
+        );
+    }
+
+    /// set the box outside of which drawing is discarded when the scissor test is enabled
+    pub fn scissor(&self, x: i32, y: i32, width: u32, height: u32) {
+        unsafe {
+            self.gl.scissor(x, y, width as _, height as _);
+        }
+        self.check_gl_error("scissor");
+    }
+
+    /// render primitives from indexed array data
+    pub fn draw_elements(&self, mode: Primitives, count: usize, kind: DataType, offset: u32) {
+        debug_check_index_offset("draw_elements", kind, offset);
+        unsafe {
+            self.gl
+                .draw_elements(mode as _, count as _, kind as _, offset as _);
+        }
+        self.check_gl_error("draw_elements");
+    }
+
+    /// render primitives from array data
+    pub fn draw_arrays(&self, mode: Primitives, count: usize) {
+        unsafe {
+            self.gl.draw_arrays(mode as _, 0, count as _);
+        }
+        self.check_gl_error("draw_arrays");
+    }
+
+    /// render several instances of primitives from array data
+    pub fn draw_arrays_instanced(&self, mode: Primitives, count: usize, instances: usize) {
+        unsafe {
+            self.gl
+                .draw_arrays_instanced(mode as _, 0, count as _, instances as _);
+        }
+        self.check_gl_error("draw_arrays_instanced");
+    }
+
+    /// render several instances of primitives from indexed array data
+    pub fn draw_elements_instanced(
+        &self,
+        mode: Primitives,
+        count: usize,
+        kind: DataType,
+        offset: u32,
+        instances: usize,
+    ) {
+        debug_check_index_offset("draw_elements_instanced", kind, offset);
+        unsafe {
+            self.gl.draw_elements_instanced(
+                mode as _,
+                count as _,
+                kind as _,
+                offset as _,
+                instances as _,
+            );
+        }
+        self.check_gl_error("draw_elements_instanced");
+    }
+
+    /// render primitives from indexed array data, all indices being in the range [start, end]
+    pub fn draw_range_elements(
+        &self,
+        mode: Primitives,
+        _start: u32,
+        _end: u32,
+        count: usize,
+        kind: DataType,
+        offset: u32,
+    ) {
+        debug_check_index_offset("draw_range_elements", kind, offset);
+        // glow does not expose glDrawRangeElements, the range is only an optimization hint
+        self.draw_elements(mode, count, kind, offset);
+    }
+
+    /// set the number of instances sharing each value of a vertex attribute. 0 means one value per vertex
+    pub fn vertex_attrib_divisor(&self, location: u32, divisor: u32) {
+        unsafe {
+            self.gl.vertex_attrib_divisor(location, divisor);
+        }
+        self.check_gl_error("vertex_attrib_divisor");
+    }
+
+    /// specify the layout of a vertex attribute, independently of the buffer it is read from. Requires OpenGL 4.3
+    pub fn vertex_attrib_format(
+        &self,
+        location: u32,
+        size: AttributeSize,
+        kind: DataType,
+        normalized: bool,
+        relative_offset: u32,
+    ) {
+        unsafe {
+            self.gl.vertex_attrib_format_f32(
+                location,
+                size as _,
+                kind as _,
+                normalized,
+                relative_offset,
+            );
+        }
+        self.check_gl_error("vertex_attrib_format");
+    }
+
+    /// read a vertex attribute from the buffer bound to a vertex buffer binding index. Requires OpenGL 4.3
+    pub fn vertex_attrib_binding(&self, location: u32, binding_index: u32) {
+        unsafe {
+            self.gl.vertex_attrib_binding(location, binding_index);
+        }
+        self.check_gl_error("vertex_attrib_binding");
+    }
+
+    /// bind a buffer to a vertex buffer binding index. Requires OpenGL 4.3
+    pub fn bind_vertex_buffer(
+        &self,
+        binding_index: u32,
+        buffer: &WebGLBuffer,
+        offset: usize,
+        stride: u32,
+    ) {
+        unsafe {
+            self.gl.bind_vertex_buffer(
+                binding_index,
+                object(buffer.0, glow::NativeBuffer),
+                offset as _,
+                stride as _,
+            );
+        }
+        self.check_gl_error("bind_vertex_buffer");
+    }
+
+    /// read a block of pixels from the frame buffer
+    pub fn read_pixels(
+        &self,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+        format: PixelFormat,
+        kind: PixelType,
+        data: &mut [u8],
+    ) {
+        let alignment =
+            unsafe { self.gl.get_parameter_i32(Parameter::PackAlignment as _) } as usize;
+        check_pixels_len(
+            "read_pixels",
+            data.len(),
+            width,
+            height,
+            format,
+            kind,
+            PixelLayout {
+                alignment,
+                ..PixelLayout::default()
+            },
+        );
+        unsafe {
+            self.gl.read_pixels(
+                x as _,
+                y as _,
+                width as _,
+                height as _,
+                format as _,
+                kind as _,
+                glow::PixelPackData::Slice(Some(data)),
+            );
+        }
+        self.check_gl_error("read_pixels");
+    }
+
+    /// read pixels into the bound pixel pack buffer at the given byte offset
+    #[allow(clippy::too_many_arguments)]
+    pub fn read_pixels_to_buffer(
+        &self,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+        format: PixelFormat,
+        kind: PixelType,
+        offset: u32,
+    ) {
+        unsafe {
+            self.gl.read_pixels(
+                x as _,
+                y as _,
+                width as _,
+                height as _,
+                format as _,
+                kind as _,
+                glow::PixelPackData::BufferOffset(offset),
+            );
+        }
+        self.check_gl_error("read_pixels_to_buffer");
+    }
+
+    /// set pixel storage modes
+    pub fn pixel_storei(&self, storage: PixelStorageMode, value: i32) {
+        unsafe {
+            self.gl.pixel_store_i32(storage as _, value);
+        }
+        self.check_gl_error("pixel_storei");
+    }
+
+    /// return the value of a pixel storage mode
+    pub fn get_pixel_storei(&self, storage: PixelStorageMode) -> i32 {
+        let value = unsafe { self.gl.get_parameter_i32(storage as _) };
+        self.check_gl_error("get_pixel_storei");
+        value
+    }
+
+    /// specify a two-dimensional texture image
+    pub fn tex_image2d(
+        &self,
+        target: TextureBindPoint,
+        level: u8,
+        width: u16,
+        height: u16,
+        format: PixelFormat,
+        kind: PixelType,
+        pixels: &[u8],
+    ) {
+        let internal_format = match format {
+            PixelFormat::DepthStencil => PixelFormat::Depth24Stencil8,
+            _ => format,
+        };
+        self.tex_image2d_ext(
+            target,
+            level,
+            internal_format,
+            width,
+            height,
+            format,
+            kind,
+            pixels,
+        );
+    }
+
+    /// specify a two-dimensional texture image with an internal format different from the pixels format.
+    ///
+    /// Use this for sized internal formats like [`PixelFormat::Rgba8`] or [`PixelFormat::R16f`]
+    #[allow(clippy::too_many_arguments)]
+    pub fn tex_image2d_ext(
+        &self,
+        target: TextureBindPoint,
+        level: u8,
+        internal_format: PixelFormat,
+        width: u16,
+        height: u16,
+        format: PixelFormat,
+        kind: PixelType,
+        pixels: &[u8],
+    ) {
+        if !pixels.is_empty() {
+            self.debug_check_unpack_len("tex_image2d", pixels, width, height, format, kind);
+        }
+        let pixels = if pixels.is_empty() {
+            None
+        } else {
+            Some(pixels)
+        };
+        unsafe {
+            self.gl.tex_image_2d(
+                target as _,
+                level as _,
+                internal_format as _,
+                width as _,
+                height as _,
+                0,
+                format as _,
+                kind as _,
+                glow::PixelUnpackData::Slice(pixels),
+            );
+        }
+        self.check_gl_error("tex_image2d");
+    }
+
+    // in debug builds, panic if the pixels are too small for the uploaded image
+    fn debug_check_unpack_len(
+        &self,
+        context: &str,
+        pixels: &[u8],
+        width: u16,
+        height: u16,
+        format: PixelFormat,
+        kind: PixelType,
+    ) {
+        if cfg!(debug_assertions) {
+            let get =
+                |mode: PixelStorageMode| unsafe { self.gl.get_parameter_i32(mode as _) as usize };
+            let layout = PixelLayout {
+                alignment: get(PixelStorageMode::UnpackAlignment),
+                row_length: get(PixelStorageMode::UnpackRowLength),
+                skip_rows: get(PixelStorageMode::UnpackSkipRows),
+                skip_pixels: get(PixelStorageMode::UnpackSkipPixels),
+            };
+            check_pixels_len(
+                context,
+                pixels.len(),
+                width as _,
+                height as _,
+                format,
+                kind,
+                layout,
+            );
+        }
+    }
+
+    /// update a part of a two-dimensional texture subimage
+    pub fn tex_sub_image2d(
+        &self,
+        target: TextureBindPoint,
+        level: u8,
+        xoffset: u16,
+        yoffset: u16,
+        width: u16,
+        height: u16,
+        format: PixelFormat,
+        kind: PixelType,
+        pixels: &[u8],
+    ) {
+        // no pixels when they are sourced from the bound pixel unpack buffer
+        let pixels = if pixels.is_empty() {
+            None
+        } else {
+            self.debug_check_unpack_len("tex_sub_image2d", pixels, width, height, format, kind);
+            Some(pixels)
+        };
+        unsafe {
+            self.gl.tex_sub_image_2d(
+                target as _,
+                level as _,
+                xoffset as _,
+                yoffset as _,
+                width as _,
+                height as _,
+                format as _,
+                kind as _,
+                glow::PixelUnpackData::Slice(pixels),
+            );
+        }
+        self.check_gl_error("tex_sub_image2d");
+    }
+
+    /// specify a two-dimensional texture image in a compressed format
+    pub fn compressed_tex_image2d(
+        &self,
+        target: TextureBindPoint,
+        level: u8,
+        compression: TextureCompression,
+        width: u16,
+        height: u16,
+        data: &[u8],
+    ) {
+        // core profiles may not list every format they support, so let the driver decide
+        if !self.is_compressed_format_listed(compression) {
+            self.log_at(
+                LogLevel::Error,
+                format!(
+                    "WARNING compressed_tex_image2d : compressed texture format {:?} is not listed by the driver",
+                    compression
+                ),
+            );
+        }
+        unsafe {
+            self.gl.compressed_tex_image_2d(
+                target as _,
+                level as _,
+                compression as _,
+                width as _,
+                height as _,
+                0,
+                data.len() as _,
+                data,
+            );
+        }
+        self.check_gl_error("compressed_tex_image2d");
+    }
+
+    /// update a part of a two-dimensional texture image in a compressed format
+    #[allow(clippy::too_many_arguments)]
+    pub fn compressed_tex_sub_image2d(
+        &self,
+        target: TextureBindPoint,
+        level: u8,
+        xoffset: u16,
+        yoffset: u16,
+        width: u16,
+        height: u16,
+        compression: TextureCompression,
+        data: &[u8],
+    ) {
+        unsafe {
+            self.gl.compressed_tex_sub_image_2d(
+                target as _,
+                level as _,
+                xoffset as _,
+                yoffset as _,
+                width as _,
+                height as _,
+                compression as _,
+                glow::CompressedPixelUnpackData::Slice(data),
+            );
+        }
+        self.check_gl_error("compressed_tex_sub_image2d");
+    }
+
+    /// copy a region of `size` texels (width, height and depth) of a texture into another
+    /// texture without going through a framebuffer.
+    ///
+    /// Requires OpenGL 4.3 or ARB_copy_image
+    pub fn copy_image_sub_data(&self, src: ImageRegion, dst: ImageRegion, size: [u32; 3]) {
+        let src_texture = self.required("copy_image_sub_data", src.texture.0, glow::NativeTexture);
+        let dst_texture = self.required("copy_image_sub_data", dst.texture.0, glow::NativeTexture);
+        if let (Some(src_texture), Some(dst_texture)) = (src_texture, dst_texture) {
+            unsafe {
+                self.gl.copy_image_sub_data(
+                    src_texture,
+                    src.target as _,
+                    src.level as _,
+                    src.offset[0],
+                    src.offset[1],
+                    src.offset[2],
+                    dst_texture,
+                    dst.target as _,
+                    dst.level as _,
+                    dst.offset[0],
+                    dst.offset[1],
+                    dst.offset[2],
+                    size[0] as _,
+                    size[1] as _,
+                    size[2] as _,
+                );
+            }
+            self.check_gl_error("copy_image_sub_data");
+        }
+    }
+
+    /// return the list of compressed texture formats supported by the driver
+    pub fn get_supported_compressed_formats(&self) -> Vec<u32> {
+        let mut formats = Vec::new();
+        unsafe {
+            let count = self
+                .gl
+                .get_parameter_i32(Parameter::NumCompressedTextureFormats as _);
+            formats.resize(count as usize, 0);
+            if count > 0 {
+                self.gl.get_parameter_i32_slice(
+                    Parameter::CompressedTextureFormats as _,
+                    &mut formats,
+                );
+            }
+        }
+        self.check_gl_error("get_supported_compressed_formats");
+        formats.into_iter().map(|f| f as u32).collect()
+    }
+
+    /// return the sample counts supported for a multisampled renderbuffer of the given format
+    pub fn get_internalformat_samples(&self, internal_format: PixelFormat) -> Vec<i32> {
+        let mut count = [0];
+        unsafe {
+            self.gl.get_internal_format_i32_slice(
+                glow::RENDERBUFFER,
+                internal_format as _,
+                glow::NUM_SAMPLE_COUNTS,
+                &mut count,
+            );
+        }
+        let mut samples = vec![0; count[0].max(0) as usize];
+        if !samples.is_empty() {
+            unsafe {
+                self.gl.get_internal_format_i32_slice(
+                    glow::RENDERBUFFER,
+                    internal_format as _,
+                    glow::SAMPLES,
+                    &mut samples,
+                );
+            }
+        }
+        self.check_gl_error("get_internalformat_samples");
+        samples
+    }
+
+    /// return informations about current program
+    pub fn get_program_parameter(&self, program: &WebGLProgram, pname: ShaderParameter) -> i32 {
+        let program = match self.required("get_program_parameter", program.0, glow::NativeProgram) {
+            Some(program) => program,
+            None => return 0,
+        };
+        let res = unsafe { self.gl.get_program_parameter_i32(program, pname as _) };
+        self.check_gl_error("get_program_parameter");
+        res
+    }
+
+    /// return the name of an active uniform block of a program
+    pub fn get_active_uniform_block_name(&self, program: &WebGLProgram, index: u32) -> String {
+        let program = match self.required(
+            "get_active_uniform_block_name",
+            program.0,
+            glow::NativeProgram,
+        ) {
+            Some(program) => program,
+            None => return String::new(),
+        };
+        let name = unsafe { self.gl.get_active_uniform_block_name(program, index) };
+        self.check_gl_error("get_active_uniform_block_name");
+        name
+    }
+
+    /// return the index of each named uniform of a program, None for inactive uniforms
+    pub fn get_uniform_indices(&self, program: &WebGLProgram, names: &[&str]) -> Vec<Option<u32>> {
+        let program = match self.required("get_uniform_indices", program.0, glow::NativeProgram) {
+            Some(program) => program,
+            None => return vec![None; names.len()],
+        };
+        let indices = unsafe { self.gl.get_uniform_indices(program, names) };
+        self.check_gl_error("get_uniform_indices");
+        indices
+    }
+
+    /// return a parameter of an active uniform block of a program.
+    /// [`UniformBlockParam::ActiveUniformIndices`] returns one value per active uniform of the block
+    pub fn get_active_uniform_block_iv(
+        &self,
+        program: &WebGLProgram,
+        index: u32,
+        pname: UniformBlockParam,
+    ) -> Vec<i32> {
+        let count = match pname {
+            UniformBlockParam::ActiveUniformIndices => {
+                self.get_active_uniform_block_iv(program, index, UniformBlockParam::ActiveUniforms)
+                    [0] as usize
+            }
+            _ => 1,
+        };
+        let mut res = vec![0; count];
+        if let Some(program) = self.required(
+            "get_active_uniform_block_iv",
+            program.0,
+            glow::NativeProgram,
+        ) {
+            unsafe {
+                self.gl.get_active_uniform_block_parameter_i32_slice(
+                    program, index, pname as _, &mut res,
+                );
+            }
+            self.check_gl_error("get_active_uniform_block_iv");
+        }
+        res
+    }
+
+    /// create a new texture object
+    pub fn create_texture(&self) -> WebGLTexture {
+        let texture = unsafe { self.gl.create_texture() }.unwrap();
+        self.check_gl_error("create_texture");
+        WebGLTexture(texture.0.get())
+    }
+
+    /// destroy a texture object
+    pub fn delete_texture(&self, texture: &WebGLTexture) {
+        unsafe {
+            if let Some(texture) = object(texture.0, glow::NativeTexture) {
+                self.gl.delete_texture(texture);
+            }
+        }
+        self.check_gl_error("delete_texture");
+    }
+
+    /// generate mipmaps for current 2D texture
+    pub fn generate_mipmap(&self) {
+        unsafe {
+            self.gl.generate_mipmap(glow::TEXTURE_2D);
+        }
+        self.check_gl_error("generate_mipmap");
+    }
+
+    /// generate mipmaps for current cube map texture
+    pub fn generate_mipmap_cube(&self) {
+        unsafe {
+            self.gl.generate_mipmap(glow::TEXTURE_CUBE_MAP);
+        }
+        self.check_gl_error("generate_mipmap_cube");
+    }
+
+    /// select active texture unit
+    pub fn active_texture(&self, active: u32) {
+        debug_assert!(
+            active < self.max_combined_texture_units(),
+            "active_texture : texture unit {} is out of range, the implementation supports {} units",
+            active,
+            self.max_combined_texture_units()
+        );
+        unsafe {
+            self.gl.active_texture(glow::TEXTURE0 + active);
+        }
+        self.check_gl_error("active_texture");
+    }
+
+    /// return the number of texture units usable by [`GLContext::active_texture`]
+    pub fn max_combined_texture_units(&self) -> u32 {
+        unsafe {
+            self.gl
+                .get_parameter_i32(Parameter::MaxCombinedTextureImageUnits as _) as u32
+        }
+    }
+
+    /// return the active texture unit, as passed to [`GLContext::active_texture`]
+    pub fn get_active_texture(&self) -> u32 {
+        unsafe { self.gl.get_parameter_i32(Parameter::ActiveTexture as _) as u32 - glow::TEXTURE0 }
+    }
+
+    /// bind a named 2D texture to a texturing target
+    pub fn bind_texture(&self, texture: &WebGLTexture) {
+        unsafe {
+            self.gl
+                .bind_texture(glow::TEXTURE_2D, object(texture.0, glow::NativeTexture));
+        }
+        self.check_gl_error("bind_texture");
+    }
+
+    /// current 2D texture is not bound to current state anymore
+    pub fn unbind_texture(&self) {
+        unsafe {
+            self.gl.bind_texture(glow::TEXTURE_2D, None);
+        }
+        self.check_gl_error("unbind_texture");
+    }
+
+    /// bind a named cube map texture to a texturing target
+    pub fn bind_texture_cube(&self, texture: &WebGLTexture) {
+        unsafe {
+            self.gl.bind_texture(
+                glow::TEXTURE_CUBE_MAP,
+                object(texture.0, glow::NativeTexture),
+            );
+        }
+        self.check_gl_error("bind_texture_cube");
+    }
+
+    /// current cube map texture is not bound to current state anymore
+    pub fn unbind_texture_cube(&self) {
+        unsafe {
+            self.gl.bind_texture(glow::TEXTURE_CUBE_MAP, None);
+        }
+        self.check_gl_error("unbind_texture_cube");
+    }
+
+    /// set the RGB alpha blend equation
+    pub fn blend_equation(&self, eq: BlendEquation) {
+        unsafe {
+            self.gl.blend_equation(eq as _);
+        }
+        self.check_gl_error("blend_equation");
+    }
+
+    /// specify pixel arithmetic for RGB and alpha components separately
+    pub fn blend_func(&self, b1: BlendMode, b2: BlendMode) {
+        unsafe {
+            self.gl.blend_func(b1 as _, b2 as _);
+        }
+        self.check_gl_error("blend_func");
+    }
+
+    /// set the blend equation of one draw buffer (OpenGL 4.0)
+    pub fn blend_equation_indexed(&self, buf: u32, eq: BlendEquation) {
+        unsafe {
+            self.gl.blend_equation_draw_buffer(buf, eq as _);
+        }
+        self.check_gl_error("blend_equation_indexed");
+    }
+
+    /// set the blend function of one draw buffer (OpenGL 4.0)
+    pub fn blend_func_indexed(&self, buf: u32, src: BlendMode, dst: BlendMode) {
+        unsafe {
+            self.gl.blend_func_draw_buffer(buf, src as _, dst as _);
+        }
+        self.check_gl_error("blend_func_indexed");
+    }
+
+    /// enable a capability for one draw buffer only, for example [`Flag::Blend`]
+    pub fn enable_indexed(&self, flag: Flag, buf: u32) {
+        self.forget_flag_cache(flag as i32);
+        unsafe {
+            self.gl.enable_draw_buffer(flag as _, buf);
+        }
+        self.check_gl_error("enable_indexed");
+    }
+
+    /// disable a capability for one draw buffer only
+    pub fn disable_indexed(&self, flag: Flag, buf: u32) {
+        self.forget_flag_cache(flag as i32);
+        unsafe {
+            self.gl.disable_draw_buffer(flag as _, buf);
+        }
+        self.check_gl_error("disable_indexed");
+    }
+
+    /// set the blend color
+    pub fn blend_color(&self, r: f32, g: f32, b: f32, a: f32) {
+        unsafe {
+            self.gl.blend_color(r, g, b, a);
+        }
+        self.check_gl_error("blend_color");
+    }
+
+    /// specify the value of a mat4 uniform variable for the current program object
+    pub fn uniform_matrix_4fv(&self, location: &WebGLUniformLocation, value: &[[f32; 4]; 4]) {
+        self.debug_check_program_in_use("uniform_matrix_4fv");
+        unsafe {
+            self.gl.uniform_matrix_4_f32_slice(
+                Some(&glow::NativeUniformLocation(location.reference)),
+                false,
+                value.as_flattened(),
+            );
+        }
+        self.check_gl_error("uniform_matrix_4fv");
+    }
+
+    /// specify the value of a mat3 uniform variable for the current program object
+    pub fn uniform_matrix_3fv(&self, location: &WebGLUniformLocation, value: &[[f32; 3]; 3]) {
+        self.debug_check_program_in_use("uniform_matrix_3fv");
+        unsafe {
+            self.gl.uniform_matrix_3_f32_slice(
+                Some(&glow::NativeUniformLocation(location.reference)),
+                false,
+                value.as_flattened(),
+            );
+        }
+        self.check_gl_error("uniform_matrix_3fv");
+    }
+
+    /// specify the value of a mat2 uniform variable for the current program object
+    pub fn uniform_matrix_2fv(&self, location: &WebGLUniformLocation, value: &[[f32; 2]; 2]) {
+        self.debug_check_program_in_use("uniform_matrix_2fv");
+        unsafe {
+            self.gl.uniform_matrix_2_f32_slice(
+                Some(&glow::NativeUniformLocation(location.reference)),
+                false,
+                value.as_flattened(),
+            );
+        }
+        self.check_gl_error("uniform_matrix_2fv");
+    }
+
+    /// specify the value of a mat2x3 uniform variable (or array of mat2x3) for the current program object
+    pub fn uniform_matrix_2x3fv(&self, location: &WebGLUniformLocation, value: &[f32]) {
+        self.debug_check_program_in_use("uniform_matrix_2x3fv");
+        unsafe {
+            self.gl.uniform_matrix_2x3_f32_slice(
+                Some(&glow::NativeUniformLocation(location.reference)),
+                false,
+                value,
+            );
+        }
+        self.check_gl_error("uniform_matrix_2x3fv");
+    }
+
+    /// specify the value of a mat3x2 uniform variable (or array of mat3x2) for the current program object
+    pub fn uniform_matrix_3x2fv(&self, location: &WebGLUniformLocation, value: &[f32]) {
+        self.debug_check_program_in_use("uniform_matrix_3x2fv");
+        unsafe {
+            self.gl.uniform_matrix_3x2_f32_slice(
+                Some(&glow::NativeUniformLocation(location.reference)),
+                false,
+                value,
+            );
+        }
+        self.check_gl_error("uniform_matrix_3x2fv");
+    }
+
+    /// specify the value of a mat2x4 uniform variable (or array of mat2x4) for the current program object
+    pub fn uniform_matrix_2x4fv(&self, location: &WebGLUniformLocation, value: &[f32]) {
+        self.debug_check_program_in_use("uniform_matrix_2x4fv");
+        unsafe {
+            self.gl.uniform_matrix_2x4_f32_slice(
+                Some(&glow::NativeUniformLocation(location.reference)),
+                false,
+                value,
+            );
+        }
+        self.check_gl_error("uniform_matrix_2x4fv");
+    }
+
+    /// specify the value of a mat4x2 uniform variable (or array of mat4x2) for the current program object
+    pub fn uniform_matrix_4x2fv(&self, location: &WebGLUniformLocation, value: &[f32]) {
+        self.debug_check_program_in_use("uniform_matrix_4x2fv");
+        unsafe {
+            self.gl.uniform_matrix_4x2_f32_slice(
+                Some(&glow::NativeUniformLocation(location.reference)),
+                false,
+                value,
+            );
+        }
+        self.check_gl_error("uniform_matrix_4x2fv");
+    }
+
+    /// specify the value of a mat3x4 uniform variable (or array of mat3x4) for the current program object
+    pub fn uniform_matrix_3x4fv(&self, location: &WebGLUniformLocation, value: &[f32]) {
+        self.debug_check_program_in_use("uniform_matrix_3x4fv");
+        unsafe {
+            self.gl.uniform_matrix_3x4_f32_slice(
+                Some(&glow::NativeUniformLocation(location.reference)),
+                false,
+                value,
+            );
+        }
+        self.check_gl_error("uniform_matrix_3x4fv");
+    }
+
+    /// specify the value of a mat4x3 uniform variable (or array of mat4x3) for the current program object
+    pub fn uniform_matrix_4x3fv(&self, location: &WebGLUniformLocation, value: &[f32]) {
+        self.debug_check_program_in_use("uniform_matrix_4x3fv");
+        unsafe {
+            self.gl.uniform_matrix_4x3_f32_slice(
+                Some(&glow::NativeUniformLocation(location.reference)),
+                false,
+                value,
+            );
+        }
+        self.check_gl_error("uniform_matrix_4x3fv");
+    }
+
+    /// specify the value of an int uniform variable for the current program object
+    pub fn uniform_1i(&self, location: &WebGLUniformLocation, value: i32) {
+        self.debug_check_program_in_use("uniform_1i");
+        unsafe {
+            self.gl.uniform_1_i32(
+                Some(&glow::NativeUniformLocation(location.reference)),
+                value,
+            );
+        }
+        self.check_gl_error("uniform_1i");
+    }
+
+    /// specify the value of a float uniform variable for the current program object
+    pub fn uniform_1f(&self, location: &WebGLUniformLocation, value: f32) {
+        self.debug_check_program_in_use("uniform_1f");
+        unsafe {
+            self.gl.uniform_1_f32(
+                Some(&glow::NativeUniformLocation(location.reference)),
+                value,
+            );
+        }
+        self.check_gl_error("uniform_1f");
+    }
+
+    /// specify the value of a vec2 uniform variable for the current program object
+    pub fn uniform_2f(&self, location: &WebGLUniformLocation, value: (f32, f32)) {
+        self.debug_check_program_in_use("uniform_2f");
+        unsafe {
+            self.gl.uniform_2_f32(
+                Some(&glow::NativeUniformLocation(location.reference)),
+                value.0,
+                value.1,
+            );
+        }
+        self.check_gl_error("uniform_2f");
+    }
+
+    /// specify the value of a vec3 uniform variable for the current program object
+    pub fn uniform_3f(&self, location: &WebGLUniformLocation, value: (f32, f32, f32)) {
+        self.debug_check_program_in_use("uniform_3f");
+        unsafe {
+            self.gl.uniform_3_f32(
+                Some(&glow::NativeUniformLocation(location.reference)),
+                value.0,
+                value.1,
+                value.2,
+            );
+        }
+        self.check_gl_error("uniform_3f");
+    }
+
+    /// specify the value of a vec4 uniform variable for the current program object
+    pub fn uniform_4f(&self, location: &WebGLUniformLocation, value: (f32, f32, f32, f32)) {
+        self.debug_check_program_in_use("uniform_4f");
+        unsafe {
+            self.gl.uniform_4_f32(
+                Some(&glow::NativeUniformLocation(location.reference)),
+                value.0,
+                value.1,
+                value.2,
+                value.3,
+            );
+        }
+        self.check_gl_error("uniform_4f");
+    }
+
+    /// specify the value of a uint uniform variable for the current program object
+    pub fn uniform_1ui(&self, location: &WebGLUniformLocation, value: u32) {
+        self.debug_check_program_in_use("uniform_1ui");
+        unsafe {
+            self.gl.uniform_1_u32(
+                Some(&glow::NativeUniformLocation(location.reference)),
+                value,
+            );
+        }
+        self.check_gl_error("uniform_1ui");
+    }
+
+    /// specify the value of a uvec2 uniform variable for the current program object
+    pub fn uniform_2ui(&self, location: &WebGLUniformLocation, value: (u32, u32)) {
+        self.debug_check_program_in_use("uniform_2ui");
+        unsafe {
+            self.gl.uniform_2_u32(
+                Some(&glow::NativeUniformLocation(location.reference)),
+                value.0,
+                value.1,
+            );
+        }
+        self.check_gl_error("uniform_2ui");
+    }
+
+    /// specify the value of a uvec3 uniform variable for the current program object
+    pub fn uniform_3ui(&self, location: &WebGLUniformLocation, value: (u32, u32, u32)) {
+        self.debug_check_program_in_use("uniform_3ui");
+        unsafe {
+            self.gl.uniform_3_u32(
+                Some(&glow::NativeUniformLocation(location.reference)),
+                value.0,
+                value.1,
+                value.2,
+            );
+        }
+        self.check_gl_error("uniform_3ui");
+    }
+
+    /// specify the value of a uvec4 uniform variable for the current program object
+    pub fn uniform_4ui(&self, location: &WebGLUniformLocation, value: (u32, u32, u32, u32)) {
+        self.debug_check_program_in_use("uniform_4ui");
+        unsafe {
+            self.gl.uniform_4_u32(
+                Some(&glow::NativeUniformLocation(location.reference)),
+                value.0,
+                value.1,
+                value.2,
+                value.3,
+            );
+        }
+        self.check_gl_error("uniform_4ui");
+    }
+
+    /// specify the values of an array of uint uniform variables for the current program object
+    pub fn uniform_1uiv(&self, location: &WebGLUniformLocation, value: &[u32]) {
+        self.debug_check_program_in_use("uniform_1uiv");
+        unsafe {
+            self.gl.uniform_1_u32_slice(
+                Some(&glow::NativeUniformLocation(location.reference)),
+                value,
+            );
+        }
+        self.check_gl_error("uniform_1uiv");
+    }
+
+    /// specify the values of an array of uvec2 uniform variables for the current program object
+    pub fn uniform_2uiv(&self, location: &WebGLUniformLocation, value: &[u32]) {
+        self.debug_check_program_in_use("uniform_2uiv");
+        unsafe {
+            self.gl.uniform_2_u32_slice(
+                Some(&glow::NativeUniformLocation(location.reference)),
+                value,
+            );
+        }
+        self.check_gl_error("uniform_2uiv");
+    }
+
+    /// specify the values of an array of uvec3 uniform variables for the current program object
+    pub fn uniform_3uiv(&self, location: &WebGLUniformLocation, value: &[u32]) {
+        self.debug_check_program_in_use("uniform_3uiv");
+        unsafe {
+            self.gl.uniform_3_u32_slice(
+                Some(&glow::NativeUniformLocation(location.reference)),
+                value,
+            );
+        }
+        self.check_gl_error("uniform_3uiv");
+    }
+
+    /// specify the values of an array of uvec4 uniform variables for the current program object
+    pub fn uniform_4uiv(&self, location: &WebGLUniformLocation, value: &[u32]) {
+        self.debug_check_program_in_use("uniform_4uiv");
+        unsafe {
+            self.gl.uniform_4_u32_slice(
+                Some(&glow::NativeUniformLocation(location.reference)),
+                value,
+            );
+        }
+        self.check_gl_error("uniform_4uiv");
+    }
+
+    /// set texture integer parameters
+    pub fn tex_parameteri(&self, kind: TextureKind, pname: TextureParameter, param: i32) {
+        unsafe {
+            self.gl.tex_parameter_i32(kind as _, pname as _, param);
+        }
+        self.check_gl_error("tex_parameteri");
+    }
+
+    /// set texture float parameters
+    pub fn tex_parameterfv(&self, kind: TextureKind, pname: TextureParameter, param: f32) {
+        unsafe {
+            self.gl.tex_parameter_f32(kind as _, pname as _, param);
+        }
+        self.check_gl_error("tex_parameterfv");
+    }
+
+    /// create a vertex array object
+    pub fn create_vertex_array(&self) -> WebGLVertexArray {
+        let vao = unsafe { self.gl.create_vertex_array() }.unwrap();
+        self.check_gl_error("create_vertex_array");
+        WebGLVertexArray(vao.0.get())
+    }
+
+    /// destroy a vertex array object
+    pub fn delete_vertex_array(&self, vao: &WebGLVertexArray) {
+        unsafe {
+            if let Some(vao) = object(vao.0, glow::NativeVertexArray) {
+                self.gl.delete_vertex_array(vao);
+            }
+        }
+        self.check_gl_error("delete_vertex_array");
+    }
+
+    /// bind a vertex array object to current state
+    pub fn bind_vertex_array(&self, vao: &WebGLVertexArray) {
+        unsafe {
+            self.gl
+                .bind_vertex_array(object(vao.0, glow::NativeVertexArray));
+        }
+        self.check_gl_error("bind_vertex_array");
+    }
+
+    /// current vertex array object is not bound to the current state anymore,
+    /// the [default one](GLContext::default_vertex_array) is bound back
+    pub fn unbind_vertex_array(&self, _vao: &WebGLVertexArray) {
+        let default = self
+            .default_vertex_array
+            .as_ref()
+            .and_then(|vao| object(vao.0, glow::NativeVertexArray));
+        unsafe {
+            self.gl.bind_vertex_array(default);
+        }
+        self.check_gl_error("unbind_vertex_array");
+    }
+
+    /// specify which color buffers are to be drawn into
+    pub fn draw_buffer(&self, buffers: &[ColorBuffer]) {
+        let buffers: Vec<u32> = buffers.iter().map(|value| *value as u32).collect();
+        unsafe {
+            self.gl.draw_buffers(&buffers);
+        }
+        self.check_gl_error("draw_buffer");
+    }
+
+    /// create a new framebuffer
+    pub fn create_framebuffer(&self) -> WebGLFrameBuffer {
+        let fb = unsafe { self.gl.create_framebuffer() }.unwrap();
+        self.check_gl_error("create_framebuffer");
+        WebGLFrameBuffer(fb.0.get())
+    }
+
+    /// destroy a framebuffer
+    pub fn delete_framebuffer(&self, fb: &WebGLFrameBuffer) {
+        unsafe {
+            if let Some(fb) = object(fb.0, glow::NativeFramebuffer) {
+                self.gl.delete_framebuffer(fb);
+            }
+        }
+        self.check_gl_error("delete_framebuffer");
+    }
+
+    /// bind a framebuffer to the current state
+    pub fn bind_framebuffer(&self, buffer: Buffers, fb: &WebGLFrameBuffer) {
+        unsafe {
+            self.gl
+                .bind_framebuffer(buffer as _, object(fb.0, glow::NativeFramebuffer));
+        }
+        self.check_gl_error("bind_framebuffer");
+    }
+
+    /// attach a texture to a framebuffer
+    pub fn framebuffer_texture2d(
+        &self,
+        target: Buffers,
+        attachment: Buffers,
+        textarget: TextureBindPoint,
+        texture: &WebGLTexture,
+        level: i32,
+    ) {
+        unsafe {
+            self.gl.framebuffer_texture_2d(
+                target as _,
+                attachment as _,
+                textarget as _,
+                object(texture.0, glow::NativeTexture),
+                level,
+            );
+        }
+        self.check_gl_error("framebuffer_texture2d");
+    }
+
+    /// attach a texture to a framebuffer, rendering into it with `samples` samples per pixel
+    /// and resolving implicitly. Requires GL_EXT_multisampled_render_to_texture, which desktop drivers rarely expose.
+    /// Otherwise, render into a multisample renderbuffer and blit it into the texture
+    ///
+    /// Not supported with the glow backend, which logs an error instead
+    pub fn framebuffer_texture2d_multisample(
+        &self,
+        _target: Buffers,
+        _attachment: Buffers,
+        _textarget: TextureBindPoint,
+        _texture: &WebGLTexture,
+        _level: i32,
+        _samples: i32,
+    ) {
+        // glow does not expose glFramebufferTexture2DMultisampleEXT
+        self.log_at(
+            LogLevel::Error,
+            "ERROR framebuffer_texture2d_multisample is not supported with the glow backend",
+        );
+    }
+
+    /// unbind a framebuffer
+    pub fn unbind_framebuffer(&self, buffer: Buffers) {
+        unsafe {
+            self.gl.bind_framebuffer(buffer as _, None);
+        }
+        self.check_gl_error("unbind_framebuffer");
+    }
+
+    /// return the completeness status of the framebuffer bound to target
+    pub fn check_framebuffer_status(&self, target: Buffers) -> FramebufferStatus {
+        let status = unsafe { self.gl.check_framebuffer_status(target as _) };
+        self.check_gl_error("check_framebuffer_status");
+        FramebufferStatus::from(status)
+    }
+
+    /// create a new renderbuffer
+    pub fn create_renderbuffer(&self) -> WebGLRenderBuffer {
+        let rb = unsafe { self.gl.create_renderbuffer() }.unwrap();
+        self.check_gl_error("create_renderbuffer");
+        WebGLRenderBuffer(rb.0.get())
+    }
+
+    /// destroy a renderbuffer
+    pub fn delete_renderbuffer(&self, rb: &WebGLRenderBuffer) {
+        unsafe {
+            if let Some(rb) = object(rb.0, glow::NativeRenderbuffer) {
+                self.gl.delete_renderbuffer(rb);
+            }
+        }
+        self.check_gl_error("delete_renderbuffer");
+    }
+
+    /// bind a renderbuffer to the current state
+    pub fn bind_renderbuffer(&self, rb: &WebGLRenderBuffer) {
+        unsafe {
+            self.gl
+                .bind_renderbuffer(glow::RENDERBUFFER, object(rb.0, glow::NativeRenderbuffer));
+        }
+        self.check_gl_error("bind_renderbuffer");
+    }
+
+    /// unbind a renderbuffer
+    pub fn unbind_renderbuffer(&self) {
+        unsafe {
+            self.gl.bind_renderbuffer(glow::RENDERBUFFER, None);
+        }
+        self.check_gl_error("unbind_renderbuffer");
+    }
+
+    /// allocate the storage of the bound renderbuffer
+    pub fn renderbuffer_storage(&self, internal_format: PixelFormat, width: u16, height: u16) {
+        unsafe {
+            self.gl.renderbuffer_storage(
+                glow::RENDERBUFFER,
+                internal_format as _,
+                width as _,
+                height as _,
+            );
+        }
+        self.check_gl_error("renderbuffer_storage");
+    }
+
+    /// attach a renderbuffer to a framebuffer
+    pub fn framebuffer_renderbuffer(
+        &self,
+        target: Buffers,
+        attachment: Buffers,
+        rb: &WebGLRenderBuffer,
+    ) {
+        unsafe {
+            self.gl.framebuffer_renderbuffer(
+                target as _,
+                attachment as _,
+                glow::RENDERBUFFER,
+                object(rb.0, glow::NativeRenderbuffer),
+            );
+        }
+        self.check_gl_error("framebuffer_renderbuffer");
+    }
+}
@@ -1,14 +1,23 @@
 use gl;
 use std::os::raw::c_void;
 
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 use std::ffi::CStr;
 use std::ffi::CString;
 use std::ops::Deref;
 use std::ptr;
+use std::rc::Rc;
 use std::str;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use crate::common::*;
 use crate::glenum::*;
+use crate::shared::{
+    check_pixels_len, debug_check_index_offset, same_uniform, uniform_components, PixelLayout,
+    StateCache, UniformLocations,
+};
+use crate::util::AsGlBytes;
 
 pub type Reference = u32;
 
@@ -25,6 +34,10 @@ pub struct GLContext {
     pub reference: Reference,
     /// whether this context is a WebGL 2.0 context
     pub is_webgl2: bool,
+    pub(crate) log_level: Cell<LogLevel>,
+    pub(crate) uniform_locations: UniformLocations,
+    pub(crate) state_cache: StateCache,
+    default_vertex_array: Option<WebGLVertexArray>,
 }
 
 /// panics with a proper message if the last OpenGL call returned an error
@@ -60,6 +73,98 @@ fn get_string(param: u32) -> String {
     };
 }
 
+/// gl::GetIntegerv convenient wrapper
+fn get_integer(param: u32) -> i32 {
+    let mut res = 0;
+    unsafe {
+        gl::GetIntegerv(param, &mut res);
+    }
+    res
+}
+
+/// gl::GetIntegerv wrapper for parameters returning a rectangle
+fn get_rect(param: u32) -> Rect {
+    let mut res = [0; 4];
+    unsafe {
+        gl::GetIntegerv(param, res.as_mut_ptr());
+    }
+    Rect {
+        x: res[0],
+        y: res[1],
+        w: res[2] as u32,
+        h: res[3] as u32,
+    }
+}
+
+/// gl::GetFloatv wrapper for parameters returning a [min, max] pair
+fn get_float_range(param: u32) -> (f32, f32) {
+    let mut res = [0.0f32; 2];
+    unsafe {
+        gl::GetFloatv(param, res.as_mut_ptr());
+    }
+    (res[0], res[1])
+}
+
+// glSpecializeShader (OpenGL 4.6) is missing from the gl crate bindings, so it is loaded by hand.
+// Loaders return pointers for any name, so it is only stored when the context supports it
+static SPECIALIZE_SHADER: AtomicUsize = AtomicUsize::new(0);
+
+type SpecializeShaderFn = extern "system" fn(
+    gl::types::GLuint,
+    *const gl::types::GLchar,
+    gl::types::GLuint,
+    *const gl::types::GLuint,
+    *const gl::types::GLuint,
+);
+
+// glDepthBoundsEXT is missing too, it is only stored when GL_EXT_depth_bounds_test is supported
+static DEPTH_BOUNDS: AtomicUsize = AtomicUsize::new(0);
+
+type DepthBoundsFn = extern "system" fn(gl::types::GLclampd, gl::types::GLclampd);
+
+// glFramebufferTexture2DMultisampleEXT, mostly found on OpenGL ES drivers
+static FRAMEBUFFER_TEXTURE2D_MULTISAMPLE: AtomicUsize = AtomicUsize::new(0);
+
+type FramebufferTexture2DMultisampleFn = extern "system" fn(
+    gl::types::GLenum,
+    gl::types::GLenum,
+    gl::types::GLenum,
+    gl::types::GLuint,
+    gl::types::GLint,
+    gl::types::GLsizei,
+);
+
+/// the (major, minor) version of the context, parsed from `GL_VERSION`
+/// as `GL_MAJOR_VERSION` and `GL_MINOR_VERSION` only exist since OpenGL 3.0
+fn get_version() -> (u32, u32) {
+    let version = get_string(gl::VERSION);
+    // "4.6.0 NVIDIA 535.00" or "OpenGL ES 3.2 Mesa"
+    let mut numbers = version
+        .trim_start_matches(|c: char| !c.is_ascii_digit())
+        .split(|c: char| !c.is_ascii_digit())
+        .map(|n| n.parse().unwrap_or(0));
+    (numbers.next().unwrap_or(0), numbers.next().unwrap_or(0))
+}
+
+/// whether the context is an OpenGL ES one
+fn is_gles() -> bool {
+    get_string(gl::VERSION).starts_with("OpenGL ES")
+}
+
+/// whether the context supports the extension
+fn has_extension(name: &str) -> bool {
+    if get_version() < (3, 0) {
+        // glGetStringi and GL_NUM_EXTENSIONS were added in OpenGL (ES) 3.0
+        return get_string(gl::EXTENSIONS)
+            .split_whitespace()
+            .any(|ext| ext == name);
+    }
+    (0..get_integer(gl::NUM_EXTENSIONS) as u32).any(|i| unsafe {
+        let ext = gl::GetStringi(gl::EXTENSIONS, i);
+        !ext.is_null() && CStr::from_ptr(ext as *const _).to_bytes() == name.as_bytes()
+    })
+}
+
 pub type WebGLContext<'p> = Box<dyn 'p + for<'a> FnMut(&'a str) -> *const c_void>;
 
 impl WebGLRenderingContext {
@@ -72,33 +177,155 @@ impl WebGLRenderingContext {
     /// let gl = uni_gl::WebGLRenderingContext::new(app.canvas());
     /// ```
     pub fn new<'p>(mut loadfn: WebGLContext<'p>) -> WebGLRenderingContext {
+        let specialize_shader = loadfn("glSpecializeShader") as usize;
+        let specialize_shader_arb = loadfn("glSpecializeShaderARB") as usize;
+        let depth_bounds = loadfn("glDepthBoundsEXT") as usize;
+        let texture2d_multisample = loadfn("glFramebufferTexture2DMultisampleEXT") as usize;
         gl::load_with(move |name| loadfn(name));
+        if !is_gles() && get_version() >= (4, 6) {
+            SPECIALIZE_SHADER.store(specialize_shader, Ordering::Relaxed);
+        } else if has_extension("GL_ARB_gl_spirv") {
+            SPECIALIZE_SHADER.store(specialize_shader_arb, Ordering::Relaxed);
+        }
+        if has_extension("GL_EXT_depth_bounds_test") {
+            DEPTH_BOUNDS.store(depth_bounds, Ordering::Relaxed);
+        }
+        if has_extension("GL_EXT_multisampled_render_to_texture") {
+            FRAMEBUFFER_TEXTURE2D_MULTISAMPLE.store(texture2d_multisample, Ordering::Relaxed);
+        }
 
         WebGLRenderingContext {
             common: GLContext::new(),
         }
     }
+
+    /// create an OpenGL context that several owners can keep, see [`WebGLRenderingContext::new`].
+    ///
+    /// The context caches state in `Cell`s and `RefCell`s, so it can be shared
+    /// but must stay on the thread owning the OpenGL context.
+    pub fn new_shared<'p>(loadfn: WebGLContext<'p>) -> Rc<WebGLRenderingContext> {
+        Rc::new(WebGLRenderingContext::new(loadfn))
+    }
+
+    /// create an OpenGL context, see [`WebGLRenderingContext::new`], with its viewport
+    /// set to cover a window of the given size.
+    ///
+    /// On the web, the viewport is set from the size of the canvas.
+    pub fn new_with_size<'p>(
+        loadfn: WebGLContext<'p>,
+        width: u32,
+        height: u32,
+    ) -> WebGLRenderingContext {
+        let context = WebGLRenderingContext::new(loadfn);
+        context.viewport(0, 0, width, height);
+        context
+    }
 }
 
 impl GLContext {
     pub fn new() -> GLContext {
         //  unsafe { gl::Enable(gl::DEPTH_TEST) };
-        println!("opengl {}", get_string(gl::VERSION));
-        println!(
-            "shading language {}",
-            get_string(gl::SHADING_LANGUAGE_VERSION)
-        );
-        println!("vendor {}", get_string(gl::VENDOR));
-        GLContext {
+        // core profiles have no vertex array bound by default, where WebGL has an implicit one.
+        // Vertex array objects need OpenGL (ES) 3.0
+        let default_vertex_array = if gl::GenVertexArrays::is_loaded() && get_version() >= (3, 0) {
+            let mut vao = WebGLVertexArray(0);
+            unsafe {
+                gl::GenVertexArrays(1, &mut vao.0);
+                gl::BindVertexArray(vao.0);
+            }
+            check_gl_error("GLContext::new");
+            Some(vao)
+        } else {
+            None
+        };
+        let context = GLContext {
             reference: 0,
             is_webgl2: true,
+            log_level: Cell::new(LogLevel::default()),
+            uniform_locations: RefCell::new(HashMap::new()),
+            state_cache: StateCache::default(),
+            default_vertex_array,
+        };
+        if context.log_enabled(LogLevel::Info) {
+            context.print_gl_info();
+        }
+        context
+    }
+
+    /// return the vendor, renderer and version strings of the implementation
+    pub fn gpu_info(&self) -> GpuInfo {
+        GpuInfo {
+            vendor: get_string(gl::VENDOR),
+            renderer: get_string(gl::RENDERER),
+            version: get_string(gl::VERSION),
+            shading_language_version: get_string(gl::SHADING_LANGUAGE_VERSION),
         }
     }
 
+    /// the vertex array object bound when no other one is, like WebGL's implicit one.
+    ///
+    /// It is created with the context on OpenGL (ES) 3.0+ and must not be deleted.
+    /// None on older versions and on the web, where vertex arrays are optional
+    pub fn default_vertex_array(&self) -> Option<&WebGLVertexArray> {
+        self.default_vertex_array.as_ref()
+    }
+
+    /// the current viewport
+    pub fn get_viewport(&self) -> Rect {
+        get_rect(gl::VIEWPORT)
+    }
+
+    /// the current scissor box
+    pub fn get_scissor_box(&self) -> Rect {
+        get_rect(gl::SCISSOR_BOX)
+    }
+
+    /// the minimum and maximum widths of aliased lines
+    pub fn line_width_range(&self) -> (f32, f32) {
+        get_float_range(gl::ALIASED_LINE_WIDTH_RANGE)
+    }
+
+    /// the minimum and maximum sizes of points
+    pub fn point_size_range(&self) -> (f32, f32) {
+        // ALIASED_POINT_SIZE_RANGE was removed from the core profile
+        get_float_range(gl::POINT_SIZE_RANGE)
+    }
+
+    /// set the width of rasterized lines, clamped to [`GLContext::line_width_range`]
+    pub fn line_width(&self, width: f32) {
+        unsafe {
+            gl::LineWidth(width);
+        }
+        check_gl_error("line_width");
+    }
+
+    /// the (major, minor) OpenGL version of this context
+    pub fn gl_version(&self) -> (u32, u32) {
+        get_version()
+    }
+
+    /// whether this context uses the core profile, where deprecated features are removed
+    pub fn is_core_profile(&self) -> bool {
+        // profiles were added in OpenGL 3.2, OpenGL ES has none
+        if is_gles() || get_version() < (3, 2) {
+            return false;
+        }
+        get_integer(gl::CONTEXT_PROFILE_MASK) as u32 & gl::CONTEXT_CORE_PROFILE_BIT != 0
+    }
+
+    /// whether an optional capability is available with the OpenGL version of this context
+    pub fn supports(&self, feature: Feature) -> bool {
+        self.gl_version() >= feature.gl_version()
+    }
+
     pub fn print<T: Into<String>>(msg: T) {
         print!("{}", msg.into());
     }
 
+    fn c_string(&self, s: &str, context: &str) -> CString {
+        CString::new(self.strip_nul(s, context).into_owned()).unwrap()
+    }
+
     /// create a new OpenGL buffer
     pub fn create_buffer(&self) -> WebGLBuffer {
         let mut buffer = WebGLBuffer(0);
@@ -125,28 +352,134 @@ impl GLContext {
         check_gl_error("bind_buffer");
     }
 
-    /// fills a buffer with data.
+    /// bind an index buffer, recorded in the bound vertex array.
+    ///
+    /// Prefer it to `bind_buffer(BufferKind::ElementArray, ..)`: on WebGL 1.0, which has no vertex array,
+    /// the web backend remembers the index buffer of each vertex array and binds it again in `bind_vertex_array`
+    pub fn bind_element_buffer(&self, buffer: &WebGLBuffer) {
+        self.bind_buffer(BufferKind::ElementArray, buffer);
+    }
+
+    /// bind a buffer to an indexed binding point of a uniform or transform feedback buffer
+    pub fn bind_buffer_base(&self, kind: BufferKind, index: u32, buffer: &WebGLBuffer) {
+        unsafe {
+            gl::BindBufferBase(kind as _, index, buffer.0);
+        }
+        check_gl_error("bind_buffer_base");
+    }
+
+    /// bind a range of a buffer to an indexed binding point of a uniform or transform feedback buffer
+    pub fn bind_buffer_range(
+        &self,
+        kind: BufferKind,
+        index: u32,
+        buffer: &WebGLBuffer,
+        offset: usize,
+        size: usize,
+    ) {
+        unsafe {
+            gl::BindBufferRange(kind as _, index, buffer.0, offset as _, size as _);
+        }
+        check_gl_error("bind_buffer_range");
+    }
+
+    /// return the buffer, offset or size bound to an indexed binding point
+    pub fn get_indexed_parameter_i32(
+        &self,
+        target: BufferKind,
+        index: u32,
+        pname: IndexedParam,
+    ) -> i32 {
+        let pname = match self.indexed_pname(target, pname) {
+            Some(pname) => pname,
+            None => return 0,
+        };
+        let mut value = 0;
+        unsafe {
+            gl::GetIntegeri_v(pname, index, &mut value);
+        }
+        check_gl_error("get_indexed_parameter_i32");
+        value
+    }
+
+    /// fills a buffer with data : bytes, or a slice of numbers or [`Pod`] values.
     ///
     /// kind : see [`GLContext::bind_buffer`].
-    pub fn buffer_data(&self, kind: BufferKind, data: &[u8], draw: DrawMode) {
+    pub fn buffer_data(&self, kind: BufferKind, data: impl AsGlBytes, draw: DrawMode) {
+        let data = data.as_gl_bytes();
         unsafe {
             gl::BufferData(kind as _, data.len() as _, data.as_ptr() as _, draw as _);
         }
         check_gl_error("buffer_data");
     }
 
+    /// allocate uninitialized storage for a buffer, to be filled later with [`GLContext::buffer_sub_data`].
+    ///
+    /// kind : see [`GLContext::bind_buffer`].
+    pub fn buffer_data_size(&self, kind: BufferKind, size: usize, draw: DrawMode) {
+        unsafe {
+            gl::BufferData(kind as _, size as _, ptr::null(), draw as _);
+        }
+        check_gl_error("buffer_data_size");
+    }
+
     /// update a subset of a buffer
     ///
     /// kind : see [`GLContext::bind_buffer`].
     ///
     /// offset : offset in the buffer where data replacement will begin
-    pub fn buffer_sub_data(&self, kind: BufferKind, offset: u32, data: &[u8]) {
+    pub fn buffer_sub_data(&self, kind: BufferKind, offset: u32, data: impl AsGlBytes) {
+        let data = data.as_gl_bytes();
         unsafe {
             gl::BufferSubData(kind as _, offset as _, data.len() as _, data.as_ptr() as _);
         }
         check_gl_error("buffer_sub_data");
     }
 
+    /// return information about the buffer currently bound to kind (size or usage hint)
+    pub fn get_buffer_parameter_i32(&self, kind: BufferKind, pname: BufferParameter) -> i32 {
+        let mut res = 0;
+        unsafe {
+            gl::GetBufferParameteriv(kind as _, pname as _, &mut res);
+        }
+        check_gl_error("get_buffer_parameter_i32");
+        res
+    }
+
+    /// create an immutable storage of size bytes for the buffer bound to kind.
+    ///
+    /// flags is a combination of [`BufferAccessBit`] values. Requires OpenGL 4.4
+    pub fn buffer_storage(&self, kind: BufferKind, size: usize, flags: u32) {
+        unsafe {
+            gl::BufferStorage(kind as _, size as _, ptr::null(), flags);
+        }
+        check_gl_error("buffer_storage");
+    }
+
+    /// map a range of the buffer bound to kind into client memory.
+    ///
+    /// access is a combination of [`BufferAccessBit`] values.
+    /// The pointer is valid for length bytes until [`GLContext::unmap_buffer`] is called
+    pub fn map_buffer_range(
+        &self,
+        kind: BufferKind,
+        offset: usize,
+        length: usize,
+        access: u32,
+    ) -> *mut u8 {
+        let res = unsafe { gl::MapBufferRange(kind as _, offset as _, length as _, access) };
+        check_gl_error("map_buffer_range");
+        res as *mut u8
+    }
+
+    /// release the mapping of the buffer bound to kind
+    pub fn unmap_buffer(&self, kind: BufferKind) {
+        unsafe {
+            gl::UnmapBuffer(kind as _);
+        }
+        check_gl_error("unmap_buffer");
+    }
+
     /// this buffer is not bound to the current state anymore.
     pub fn unbind_buffer(&self, kind: BufferKind) {
         unsafe {
@@ -165,15 +498,94 @@ impl GLContext {
 
     /// set or replace the source code in a shader
     pub fn shader_source(&self, shader: &WebGLShader, source: &str) {
-        let src = CString::new(source).unwrap();
+        let src = self.c_string(source, "shader_source");
         unsafe {
             gl::ShaderSource(shader.0, 1, &src.as_ptr(), ptr::null());
         }
         check_gl_error("shader_source");
     }
 
+    /// set or replace the source code in a shader from several strings.
+    ///
+    /// The strings are concatenated by OpenGL, which keeps `#line` directives meaningful
+    pub fn shader_source_multi(&self, shader: &WebGLShader, sources: &[&str]) {
+        let srcs: Vec<CString> = sources
+            .iter()
+            .map(|source| self.c_string(source, "shader_source_multi"))
+            .collect();
+        let ptrs: Vec<*const gl::types::GLchar> = srcs.iter().map(|src| src.as_ptr()).collect();
+        unsafe {
+            gl::ShaderSource(shader.0, ptrs.len() as _, ptrs.as_ptr(), ptr::null());
+        }
+        check_gl_error("shader_source_multi");
+    }
+
+    /// load precompiled binaries into shaders, for example SPIR-V modules
+    /// (format `GL_SHADER_BINARY_FORMAT_SPIR_V` = 0x9551, OpenGL 4.6).
+    ///
+    /// SPIR-V shaders must then be specialized with [`GLContext::specialize_shader`] instead of compiled
+    pub fn shader_binary(&self, shaders: &[&WebGLShader], format: u32, binary: &[u8]) {
+        let ids: Vec<u32> = shaders.iter().map(|shader| shader.0).collect();
+        unsafe {
+            gl::ShaderBinary(
+                ids.len() as _,
+                ids.as_ptr(),
+                format,
+                binary.as_ptr() as _,
+                binary.len() as _,
+            );
+        }
+        check_gl_error("shader_binary");
+    }
+
+    /// set the entry point and the specialization constants (index, value) of a SPIR-V shader.
+    /// Takes the place of [`GLContext::compile_shader`] for shaders loaded with [`GLContext::shader_binary`]
+    pub fn specialize_shader(
+        &self,
+        shader: &WebGLShader,
+        entry_point: &str,
+        constants: &[(u32, u32)],
+    ) {
+        let ptr = SPECIALIZE_SHADER.load(Ordering::Relaxed);
+        if ptr == 0 {
+            self.log_at(
+                LogLevel::Error,
+                "ERROR specialize_shader requires OpenGL 4.6 or GL_ARB_gl_spirv",
+            );
+            return;
+        }
+        let specialize: SpecializeShaderFn = unsafe { std::mem::transmute(ptr) };
+        let entry_point = self.c_string(entry_point, "specialize_shader");
+        let (indices, values): (Vec<u32>, Vec<u32>) = constants.iter().cloned().unzip();
+        specialize(
+            shader.0,
+            entry_point.as_ptr(),
+            constants.len() as _,
+            indices.as_ptr(),
+            values.as_ptr(),
+        );
+        check_gl_error("specialize_shader");
+    }
+
+    /// return informations about a shader
+    pub fn get_shader_parameter(&self, shader: &WebGLShader, pname: ShaderParameter) -> i32 {
+        let mut res = 0;
+        unsafe {
+            gl::GetShaderiv(shader.0, pname as _, &mut res);
+        }
+        check_gl_error("get_shader_parameter");
+        res
+    }
+
     /// compile a shader
     pub fn compile_shader(&self, shader: &WebGLShader) {
+        if let Err(log) = self.try_compile_shader(shader) {
+            panic!("{}", log);
+        }
+    }
+
+    /// compile a shader, returning the info log on failure
+    pub(crate) fn try_compile_shader(&self, shader: &WebGLShader) -> Result<(), String> {
         unsafe {
             gl::CompileShader(shader.0);
 
@@ -194,14 +606,21 @@ impl GLContext {
                     buf.as_mut_ptr() as *mut gl::types::GLchar,
                 );
 
-                match String::from_utf8(buf) {
-                    Ok(s) => panic!("{}", s),
-                    Err(_) => panic!("Compile shader fail, reason unknown"),
-                }
+                return Err(String::from_utf8(buf)
+                    .unwrap_or_else(|_| "Compile shader fail, reason unknown".to_owned()));
             }
         }
 
         check_gl_error("compile_shader");
+        Ok(())
+    }
+
+    /// delete a shader. It is only destroyed once detached from every program
+    pub fn delete_shader(&self, shader: &WebGLShader) {
+        unsafe {
+            gl::DeleteShader(shader.0);
+        }
+        check_gl_error("delete_shader");
     }
 
     /// create a program
@@ -213,6 +632,14 @@ impl GLContext {
 
     /// link a program
     pub fn link_program(&self, program: &WebGLProgram) {
+        if let Err(log) = self.try_link_program(program) {
+            panic!("{}", log);
+        }
+    }
+
+    /// link a program, returning the info log on failure
+    pub(crate) fn try_link_program(&self, program: &WebGLProgram) -> Result<(), String> {
+        self.forget_uniform_locations(program);
         unsafe {
             gl::LinkProgram(program.0);
             // Get the link status
@@ -232,13 +659,21 @@ impl GLContext {
                     buf.as_mut_ptr() as *mut gl::types::GLchar,
                 );
 
-                match String::from_utf8(buf) {
-                    Ok(s) => panic!("{}", s),
-                    Err(_) => panic!("Link program fail, reason unknown"),
-                }
+                return Err(String::from_utf8(buf)
+                    .unwrap_or_else(|_| "Link program fail, reason unknown".to_owned()));
             }
         }
         check_gl_error("link_program");
+        Ok(())
+    }
+
+    /// delete a program. It is only destroyed once it is no longer in use
+    pub fn delete_program(&self, program: &WebGLProgram) {
+        self.forget_uniform_locations(program);
+        unsafe {
+            gl::DeleteProgram(program.0);
+        }
+        check_gl_error("delete_program");
     }
 
     /// bind a program to the current state.
@@ -249,6 +684,16 @@ impl GLContext {
         check_gl_error("use_program");
     }
 
+    /// whether a program is currently in use
+    pub(crate) fn is_program_in_use(&self) -> bool {
+        get_integer(gl::CURRENT_PROGRAM) != 0
+    }
+
+    /// pop the oldest error flag, gl::NO_ERROR if there is none
+    pub(crate) fn get_error(&self) -> u32 {
+        unsafe { gl::GetError() }
+    }
+
     /// attach a shader to a program. A program must have two shaders : vertex and fragment shader.
     pub fn attach_shader(&self, program: &WebGLProgram, shader: &WebGLShader) {
         unsafe {
@@ -257,18 +702,64 @@ impl GLContext {
         check_gl_error("attach_shader");
     }
 
+    /// detach a shader from a program
+    pub fn detach_shader(&self, program: &WebGLProgram, shader: &WebGLShader) {
+        unsafe {
+            gl::DetachShader(program.0, shader.0);
+        }
+        check_gl_error("detach_shader");
+    }
+
+    /// return the shaders attached to a program
+    pub fn get_attached_shaders(&self, program: &WebGLProgram) -> Vec<WebGLShader> {
+        let count = self.get_program_parameter(program, ShaderParameter::AttachedShaders);
+        let mut shaders = vec![0; count as usize];
+        let mut len = 0;
+        unsafe {
+            gl::GetAttachedShaders(program.0, count, &mut len, shaders.as_mut_ptr());
+        }
+        check_gl_error("get_attached_shaders");
+        shaders.truncate(len as usize);
+        shaders.into_iter().map(WebGLShader).collect()
+    }
+
     /// associate a generic vertex attribute index with a named attribute
     pub fn bind_attrib_location(&self, program: &WebGLProgram, name: &str, loc: u32) {
-        let c_name = CString::new(name).unwrap();
+        let c_name = self.c_string(name, "bind_attrib_location");
         unsafe {
             gl::BindAttribLocation(program.0 as _, loc as _, c_name.as_ptr());
             check_gl_error("bind_attrib_location");
         }
     }
 
+    /// bind a user-defined fragment shader output variable to a color number (draw buffer)
+    ///
+    /// must be called before [`GLContext::link_program`] to take effect.
+    pub fn bind_frag_data_location(&self, program: &WebGLProgram, color_number: u32, name: &str) {
+        let c_name = self.c_string(name, "bind_frag_data_location");
+        unsafe {
+            gl::BindFragDataLocation(program.0 as _, color_number as _, c_name.as_ptr());
+            check_gl_error("bind_frag_data_location");
+        }
+    }
+
+    /// return the color number a fragment shader output variable is bound to
+    pub fn get_frag_data_location(&self, program: &WebGLProgram, name: &str) -> Option<i32> {
+        let c_name = self.c_string(name, "get_frag_data_location");
+        unsafe {
+            let location = gl::GetFragDataLocation(program.0 as _, c_name.as_ptr());
+            check_gl_error("get_frag_data_location");
+            if location == -1 {
+                None
+            } else {
+                Some(location)
+            }
+        }
+    }
+
     /// return the location of an attribute variable
     pub fn get_attrib_location(&self, program: &WebGLProgram, name: &str) -> Option<u32> {
-        let c_name = CString::new(name).unwrap();
+        let c_name = self.c_string(name, "get_attrib_location");
         unsafe {
             let location = gl::GetAttribLocation(program.0 as _, c_name.as_ptr());
             check_gl_error("get_attrib_location");
@@ -285,7 +776,7 @@ impl GLContext {
         program: &WebGLProgram,
         name: &str,
     ) -> Option<WebGLUniformLocation> {
-        let c_name = CString::new(name).unwrap();
+        let c_name = self.c_string(name, "get_uniform_location");
         unsafe {
             let location = gl::GetUniformLocation(program.0 as _, c_name.as_ptr());
             check_gl_error(&format!("get_uniform_location {}", name));
@@ -299,6 +790,60 @@ impl GLContext {
         }
     }
 
+    /// number of values stored in the uniform called name
+    fn uniform_components(&self, program: &WebGLProgram, name: &str) -> usize {
+        let count = self.get_program_parameter(program, ShaderParameter::ActiveUniforms);
+        for index in 0..count as u32 {
+            let mut buf = [0u8; 256];
+            let (mut len, mut size, mut kind) = (0, 0, 0);
+            unsafe {
+                gl::GetActiveUniform(
+                    program.0,
+                    index,
+                    buf.len() as _,
+                    &mut len,
+                    &mut size,
+                    &mut kind,
+                    buf.as_mut_ptr() as _,
+                );
+            }
+            let active_name = String::from_utf8_lossy(&buf[..len as usize]);
+            if same_uniform(&active_name, name) {
+                return uniform_components(kind);
+            }
+        }
+        check_gl_error("get_active_uniform");
+        1
+    }
+
+    /// return the value of a float uniform (one value per component)
+    pub fn get_uniform_f32(
+        &self,
+        program: &WebGLProgram,
+        location: &WebGLUniformLocation,
+    ) -> Vec<f32> {
+        let mut res = vec![0.0; self.uniform_components(program, &location.name)];
+        unsafe {
+            gl::GetUniformfv(program.0, location.reference as _, res.as_mut_ptr());
+        }
+        check_gl_error("get_uniform_f32");
+        res
+    }
+
+    /// return the value of an integer, boolean or sampler uniform (one value per component)
+    pub fn get_uniform_i32(
+        &self,
+        program: &WebGLProgram,
+        location: &WebGLUniformLocation,
+    ) -> Vec<i32> {
+        let mut res = vec![0; self.uniform_components(program, &location.name)];
+        unsafe {
+            gl::GetUniformiv(program.0, location.reference as _, res.as_mut_ptr());
+        }
+        check_gl_error("get_uniform_i32");
+        res
+    }
+
     /// define an array of generic vertex attribute data
     pub fn vertex_attrib_pointer(
         &self,
@@ -334,8 +879,12 @@ impl GLContext {
         check_gl_error("enable_vertex_attrib_array");
     }
 
-    /// specify clear values for the color buffers
+    /// specify clear values for the color buffers. The call is skipped if the color did not change,
+    /// see [`GLContext::flush_state_cache`]
     pub fn clear_color(&self, r: f32, g: f32, b: f32, a: f32) {
+        if !self.update_clear_color_cache([r, g, b, a]) {
+            return;
+        }
         unsafe {
             gl::ClearColor(r, g, b, a);
         }
@@ -344,8 +893,12 @@ impl GLContext {
 
     /// enable GL capabilities.
     ///
-    /// flag should be one of [`Flag`]
+    /// flag should be one of [`Flag`]. The call is skipped if uni-gl already enabled it,
+    /// see [`GLContext::flush_state_cache`]
     pub fn enable(&self, flag: i32) {
+        if !self.update_flag_cache(flag, true) {
+            return;
+        }
         unsafe {
             gl::Enable(flag as _);
         }
@@ -354,14 +907,24 @@ impl GLContext {
 
     /// disable GL capabilities.
     ///
-    /// flag should be one of [`Flag`]
+    /// flag should be one of [`Flag`]. The call is skipped if uni-gl already disabled it
     pub fn disable(&self, flag: i32) {
+        if !self.update_flag_cache(flag, false) {
+            return;
+        }
         unsafe {
             gl::Disable(flag as _);
         }
         check_gl_error("disable");
     }
 
+    /// test whether a capability is enabled
+    pub fn is_enabled(&self, flag: Flag) -> bool {
+        let res = unsafe { gl::IsEnabled(flag as _) };
+        check_gl_error("is_enabled");
+        res == gl::TRUE
+    }
+
     /// specify whether front- or back-facing polygons can be culled
     pub fn cull_face(&self, flag: Culling) {
         unsafe {
@@ -378,8 +941,16 @@ impl GLContext {
         check_gl_error("depth_mask");
     }
 
+    /// enable or disable writing of the color components into the color buffer
+    pub fn color_mask(&self, r: bool, g: bool, b: bool, a: bool) {
+        unsafe {
+            gl::ColorMask(r as _, g as _, b as _, a as _);
+        }
+        check_gl_error("color_mask");
+    }
+
     /// specify the value used for depth buffer comparisons
-    pub fn depth_func(&self, d: DepthTest) {
+    pub fn depth_func(&self, d: CompareFunc) {
         unsafe {
             gl::DepthFunc(d as _);
         }
@@ -387,6 +958,25 @@ impl GLContext {
         check_gl_error("depth_func");
     }
 
+    /// return the current depth comparison function, or its raw value if uni-gl has no matching variant
+    pub fn get_depth_func(&self) -> Result<CompareFunc, u32> {
+        let func = get_integer(Parameter::DepthFunc as _);
+        check_gl_error("get_depth_func");
+        CompareFunc::try_from(func as u32)
+    }
+
+    /// return the current source and destination RGB blend factors, or the raw value of one
+    /// uni-gl has no variant for, like a factor set by other code
+    pub fn get_blend_func(&self) -> Result<(BlendMode, BlendMode), u32> {
+        let src = get_integer(Parameter::BlendSrcRgb as _);
+        let dst = get_integer(Parameter::BlendDstRgb as _);
+        check_gl_error("get_blend_func");
+        Ok((
+            BlendMode::try_from(src as u32)?,
+            BlendMode::try_from(dst as u32)?,
+        ))
+    }
+
     /// specify the clear value for the depth buffer
     pub fn clear_depth(&self, value: f32) {
         unsafe {
@@ -395,6 +985,34 @@ impl GLContext {
         check_gl_error("clear_depth");
     }
 
+    /// specify the clear value for the depth buffer, in double precision.
+    ///
+    /// The web backend truncates the value to f32 as WebGL only supports single precision.
+    pub fn clear_depth_f64(&self, value: f64) {
+        unsafe {
+            gl::ClearDepth(value);
+        }
+        check_gl_error("clear_depth_f64");
+    }
+
+    /// specify mapping of depth values from normalized device coordinates to window coordinates
+    pub fn depth_range(&self, near: f32, far: f32) {
+        unsafe {
+            gl::DepthRange(near as _, far as _);
+        }
+        check_gl_error("depth_range");
+    }
+
+    /// same as [`GLContext::depth_range`], in double precision.
+    ///
+    /// The web backend truncates the values to f32 as WebGL only supports single precision.
+    pub fn depth_range_f64(&self, near: f64, far: f64) {
+        unsafe {
+            gl::DepthRange(near, far);
+        }
+        check_gl_error("depth_range_f64");
+    }
+
     /// clear buffers to preset values
     pub fn clear(&self, bit: BufferBit) {
         unsafe {
@@ -411,8 +1029,99 @@ impl GLContext {
         check_gl_error("viewport");
     }
 
+    /// set the viewport of one of the viewports used by layered rendering (OpenGL 4.1)
+    pub fn viewport_indexed(&self, index: u32, rect: Rect) {
+        unsafe {
+            gl::ViewportIndexedf(index, rect.x as _, rect.y as _, rect.w as _, rect.h as _);
+        }
+        check_gl_error("viewport_indexed");
+    }
+
+    /// set the viewports used by layered rendering, starting from the first one (OpenGL 4.1)
+    pub fn viewport_array(&self, rects: &[Rect]) {
+        let values: Vec<f32> = rects
+            .iter()
+            .flat_map(|r| [r.x as f32, r.y as f32, r.w as f32, r.h as f32])
+            .collect();
+        unsafe {
+            gl::ViewportArrayv(0, rects.len() as _, values.as_ptr());
+        }
+        check_gl_error("viewport_array");
+    }
+
+    /// to call when the window is resized. Sets the viewport to the whole window
+    pub fn resize(&self, width: u32, height: u32) {
+        self.viewport(0, 0, width, height);
+    }
+
+    /// set the number of vertices in each patch drawn with [`Primitives::Patches`]
+    pub fn patch_parameter_vertices(&self, n: i32) {
+        unsafe {
+            gl::PatchParameteri(gl::PATCH_VERTICES, n);
+        }
+        check_gl_error("patch_parameter_vertices");
+    }
+
+    /// set the index restarting primitives when [`Flag::PrimitiveRestart`] is enabled
+    pub fn primitive_restart_index(&self, index: u32) {
+        unsafe {
+            gl::PrimitiveRestartIndex(index);
+        }
+        check_gl_error("primitive_restart_index");
+    }
+
+    /// set the minimum fraction of samples shaded independently when [`Flag::SampleShading`] is enabled
+    pub fn min_sample_shading(&self, value: f32) {
+        unsafe {
+            gl::MinSampleShading(value);
+        }
+        check_gl_error("min_sample_shading");
+    }
+
+    /// whether read_pixels clamps the colors to [0, 1]. Disable it to read back float framebuffers
+    pub fn clamp_read_color(&self, clamp: bool) {
+        let clamp = if clamp { gl::TRUE } else { gl::FALSE };
+        unsafe {
+            gl::ClampColor(gl::CLAMP_READ_COLOR, clamp as _);
+        }
+        check_gl_error("clamp_read_color");
+    }
+
+    /// set the depth range outside of which fragments are discarded when [`Flag::DepthBoundsTest`] is enabled.
+    /// Requires GL_EXT_depth_bounds_test
+    pub fn depth_bounds(&self, min: f32, max: f32) {
+        let ptr = DEPTH_BOUNDS.load(Ordering::Relaxed);
+        if ptr == 0 {
+            self.log_at(
+                LogLevel::Error,
+                "ERROR depth_bounds requires GL_EXT_depth_bounds_test",
+            );
+            return;
+        }
+        let depth_bounds: DepthBoundsFn = unsafe { std::mem::transmute(ptr) };
+        depth_bounds(min as _, max as _);
+        check_gl_error("depth_bounds");
+    }
+
+    /// set a word of the sample mask applied when [`Flag::SampleMask`] is enabled
+    pub fn sample_mask(&self, index: u32, mask: u32) {
+        unsafe {
+            gl::SampleMaski(index, mask);
+        }
+        check_gl_error("sample_mask");
+    }
+
+    /// set the box outside of which drawing is discarded when the scissor test is enabled
+    pub fn scissor(&self, x: i32, y: i32, width: u32, height: u32) {
+        unsafe {
+            gl::Scissor(x, y, width as _, height as _);
+        };
+        check_gl_error("scissor");
+    }
+
     /// render primitives from indexed array data
     pub fn draw_elements(&self, mode: Primitives, count: usize, kind: DataType, offset: u32) {
+        debug_check_index_offset("draw_elements", kind, offset);
         unsafe {
             gl::DrawElements(mode as _, count as _, kind as _, offset as _);
         };
@@ -422,9 +1131,107 @@ impl GLContext {
     /// render primitives from array data
     pub fn draw_arrays(&self, mode: Primitives, count: usize) {
         unsafe {
-            gl::DrawArrays(mode as _, 0, count as _);
-        };
-        check_gl_error("draw_arrays");
+            gl::DrawArrays(mode as _, 0, count as _);
+        };
+        check_gl_error("draw_arrays");
+    }
+
+    /// render several instances of primitives from array data
+    pub fn draw_arrays_instanced(&self, mode: Primitives, count: usize, instances: usize) {
+        unsafe {
+            gl::DrawArraysInstanced(mode as _, 0, count as _, instances as _);
+        };
+        check_gl_error("draw_arrays_instanced");
+    }
+
+    /// render several instances of primitives from indexed array data
+    pub fn draw_elements_instanced(
+        &self,
+        mode: Primitives,
+        count: usize,
+        kind: DataType,
+        offset: u32,
+        instances: usize,
+    ) {
+        debug_check_index_offset("draw_elements_instanced", kind, offset);
+        unsafe {
+            gl::DrawElementsInstanced(
+                mode as _,
+                count as _,
+                kind as _,
+                offset as _,
+                instances as _,
+            );
+        };
+        check_gl_error("draw_elements_instanced");
+    }
+
+    /// render primitives from indexed array data, all indices being in the range [start, end]
+    pub fn draw_range_elements(
+        &self,
+        mode: Primitives,
+        start: u32,
+        end: u32,
+        count: usize,
+        kind: DataType,
+        offset: u32,
+    ) {
+        debug_check_index_offset("draw_range_elements", kind, offset);
+        unsafe {
+            gl::DrawRangeElements(mode as _, start, end, count as _, kind as _, offset as _);
+        };
+        check_gl_error("draw_range_elements");
+    }
+
+    /// set the number of instances sharing each value of a vertex attribute. 0 means one value per vertex
+    pub fn vertex_attrib_divisor(&self, location: u32, divisor: u32) {
+        unsafe {
+            gl::VertexAttribDivisor(location, divisor);
+        }
+        check_gl_error("vertex_attrib_divisor");
+    }
+
+    /// specify the layout of a vertex attribute, independently of the buffer it is read from. Requires OpenGL 4.3
+    pub fn vertex_attrib_format(
+        &self,
+        location: u32,
+        size: AttributeSize,
+        kind: DataType,
+        normalized: bool,
+        relative_offset: u32,
+    ) {
+        unsafe {
+            gl::VertexAttribFormat(
+                location,
+                size as _,
+                kind as _,
+                normalized as _,
+                relative_offset,
+            );
+        }
+        check_gl_error("vertex_attrib_format");
+    }
+
+    /// read a vertex attribute from the buffer bound to a vertex buffer binding index. Requires OpenGL 4.3
+    pub fn vertex_attrib_binding(&self, location: u32, binding_index: u32) {
+        unsafe {
+            gl::VertexAttribBinding(location, binding_index);
+        }
+        check_gl_error("vertex_attrib_binding");
+    }
+
+    /// bind a buffer to a vertex buffer binding index. Requires OpenGL 4.3
+    pub fn bind_vertex_buffer(
+        &self,
+        binding_index: u32,
+        buffer: &WebGLBuffer,
+        offset: usize,
+        stride: u32,
+    ) {
+        unsafe {
+            gl::BindVertexBuffer(binding_index, buffer.0, offset as _, stride as _);
+        }
+        check_gl_error("bind_vertex_buffer");
     }
 
     /// read a block of pixels from the frame buffer
@@ -438,6 +1245,19 @@ impl GLContext {
         kind: PixelType,
         data: &mut [u8],
     ) {
+        let alignment = get_integer(Parameter::PackAlignment as _) as usize;
+        check_pixels_len(
+            "read_pixels",
+            data.len(),
+            width,
+            height,
+            format,
+            kind,
+            PixelLayout {
+                alignment,
+                ..PixelLayout::default()
+            },
+        );
         unsafe {
             gl::ReadPixels(
                 x as _,
@@ -452,6 +1272,32 @@ impl GLContext {
         }
     }
 
+    /// read pixels into the bound pixel pack buffer at the given byte offset
+    #[allow(clippy::too_many_arguments)]
+    pub fn read_pixels_to_buffer(
+        &self,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+        format: PixelFormat,
+        kind: PixelType,
+        offset: u32,
+    ) {
+        unsafe {
+            gl::ReadPixels(
+                x as _,
+                y as _,
+                width as _,
+                height as _,
+                format as _,
+                kind as _,
+                offset as usize as *mut _,
+            );
+            check_gl_error("read_pixels_to_buffer");
+        }
+    }
+
     /// set pixel storage modes
     pub fn pixel_storei(&self, storage: PixelStorageMode, value: i32) {
         unsafe {
@@ -460,6 +1306,11 @@ impl GLContext {
         }
     }
 
+    /// return the value of a pixel storage mode
+    pub fn get_pixel_storei(&self, storage: PixelStorageMode) -> i32 {
+        get_integer(storage as _)
+    }
+
     /// specify a two-dimensional texture image
     pub fn tex_image2d(
         &self,
@@ -471,6 +1322,40 @@ impl GLContext {
         kind: PixelType,
         pixels: &[u8],
     ) {
+        let internal_format = match format {
+            PixelFormat::DepthStencil => PixelFormat::Depth24Stencil8,
+            _ => format,
+        };
+        self.tex_image2d_ext(
+            target,
+            level,
+            internal_format,
+            width,
+            height,
+            format,
+            kind,
+            pixels,
+        );
+    }
+
+    /// specify a two-dimensional texture image with an internal format different from the pixels format.
+    ///
+    /// Use this for sized internal formats like [`PixelFormat::Rgba8`] or [`PixelFormat::R16f`]
+    #[allow(clippy::too_many_arguments)]
+    pub fn tex_image2d_ext(
+        &self,
+        target: TextureBindPoint,
+        level: u8,
+        internal_format: PixelFormat,
+        width: u16,
+        height: u16,
+        format: PixelFormat,
+        kind: PixelType,
+        pixels: &[u8],
+    ) {
+        if !pixels.is_empty() {
+            self.debug_check_unpack_len("tex_image2d", pixels, width, height, format, kind);
+        }
         let p: *const c_void;
 
         if pixels.len() > 0 {
@@ -483,11 +1368,11 @@ impl GLContext {
             gl::TexImage2D(
                 target as _,
                 level as _,
-                format as _, // internal format
+                internal_format as _,
                 width as _,
                 height as _,
                 0,
-                format as _, // format
+                format as _,
                 kind as _,
                 p as _,
             );
@@ -496,6 +1381,35 @@ impl GLContext {
         check_gl_error("tex_image2d");
     }
 
+    // in debug builds, panic if the pixels are too small for the uploaded image
+    fn debug_check_unpack_len(
+        &self,
+        context: &str,
+        pixels: &[u8],
+        width: u16,
+        height: u16,
+        format: PixelFormat,
+        kind: PixelType,
+    ) {
+        if cfg!(debug_assertions) {
+            let layout = PixelLayout {
+                alignment: get_integer(PixelStorageMode::UnpackAlignment as _) as usize,
+                row_length: get_integer(PixelStorageMode::UnpackRowLength as _) as usize,
+                skip_rows: get_integer(PixelStorageMode::UnpackSkipRows as _) as usize,
+                skip_pixels: get_integer(PixelStorageMode::UnpackSkipPixels as _) as usize,
+            };
+            check_pixels_len(
+                context,
+                pixels.len(),
+                width as _,
+                height as _,
+                format,
+                kind,
+                layout,
+            );
+        }
+    }
+
     /// update a part of a two-dimensional texture subimage
     pub fn tex_sub_image2d(
         &self,
@@ -509,6 +1423,13 @@ impl GLContext {
         kind: PixelType,
         pixels: &[u8],
     ) {
+        // no pixels when they are sourced from the bound pixel unpack buffer
+        let p: *const c_void = if pixels.is_empty() {
+            ptr::null()
+        } else {
+            self.debug_check_unpack_len("tex_sub_image2d", pixels, width, height, format, kind);
+            pixels.as_ptr() as _
+        };
         unsafe {
             gl::TexSubImage2D(
                 target as _,
@@ -519,7 +1440,7 @@ impl GLContext {
                 height as _,
                 format as _,
                 kind as _,
-                pixels.as_ptr() as _,
+                p,
             );
         }
 
@@ -536,6 +1457,16 @@ impl GLContext {
         height: u16,
         data: &[u8],
     ) {
+        // core profiles may not list every format they support, so let the driver decide
+        if !self.is_compressed_format_listed(compression) {
+            self.log_at(
+                LogLevel::Error,
+                format!(
+                    "WARNING compressed_tex_image2d : compressed texture format {:?} is not listed by the driver",
+                    compression
+                ),
+            );
+        }
         unsafe {
             gl::CompressedTexImage2D(
                 target as _,
@@ -552,6 +1483,107 @@ impl GLContext {
         check_gl_error("compressed_tex_image2d");
     }
 
+    /// update a part of a two-dimensional texture image in a compressed format
+    #[allow(clippy::too_many_arguments)]
+    pub fn compressed_tex_sub_image2d(
+        &self,
+        target: TextureBindPoint,
+        level: u8,
+        xoffset: u16,
+        yoffset: u16,
+        width: u16,
+        height: u16,
+        compression: TextureCompression,
+        data: &[u8],
+    ) {
+        unsafe {
+            gl::CompressedTexSubImage2D(
+                target as _,
+                level as _,
+                xoffset as _,
+                yoffset as _,
+                width as _,
+                height as _,
+                compression as _,
+                data.len() as _,
+                data.as_ptr() as _,
+            );
+        }
+
+        check_gl_error("compressed_tex_sub_image2d");
+    }
+
+    /// copy a region of `size` texels (width, height and depth) of a texture into another
+    /// texture without going through a framebuffer.
+    ///
+    /// Requires OpenGL 4.3 or ARB_copy_image
+    pub fn copy_image_sub_data(&self, src: ImageRegion, dst: ImageRegion, size: [u32; 3]) {
+        unsafe {
+            gl::CopyImageSubData(
+                src.texture.0,
+                src.target as _,
+                src.level as _,
+                src.offset[0],
+                src.offset[1],
+                src.offset[2],
+                dst.texture.0,
+                dst.target as _,
+                dst.level as _,
+                dst.offset[0],
+                dst.offset[1],
+                dst.offset[2],
+                size[0] as _,
+                size[1] as _,
+                size[2] as _,
+            );
+        }
+        check_gl_error("copy_image_sub_data");
+    }
+
+    /// return the list of compressed texture formats supported by the driver
+    pub fn get_supported_compressed_formats(&self) -> Vec<u32> {
+        let count = get_integer(Parameter::NumCompressedTextureFormats as _);
+        let mut formats = vec![0; count as usize];
+        if count > 0 {
+            unsafe {
+                gl::GetIntegerv(
+                    Parameter::CompressedTextureFormats as _,
+                    formats.as_mut_ptr(),
+                );
+            }
+        }
+        check_gl_error("get_supported_compressed_formats");
+        formats.into_iter().map(|f| f as u32).collect()
+    }
+
+    /// return the sample counts supported for a multisampled renderbuffer of the given format
+    pub fn get_internalformat_samples(&self, internal_format: PixelFormat) -> Vec<i32> {
+        let mut count = 0;
+        unsafe {
+            gl::GetInternalformativ(
+                gl::RENDERBUFFER,
+                internal_format as _,
+                gl::NUM_SAMPLE_COUNTS,
+                1,
+                &mut count,
+            );
+        }
+        let mut samples = vec![0; count.max(0) as usize];
+        if count > 0 {
+            unsafe {
+                gl::GetInternalformativ(
+                    gl::RENDERBUFFER,
+                    internal_format as _,
+                    gl::SAMPLES,
+                    count,
+                    samples.as_mut_ptr(),
+                );
+            }
+        }
+        check_gl_error("get_internalformat_samples");
+        samples
+    }
+
     /// return informations about current program
     pub fn get_program_parameter(&self, program: &WebGLProgram, pname: ShaderParameter) -> i32 {
         let mut res = 0;
@@ -563,6 +1595,81 @@ impl GLContext {
         res
     }
 
+    /// return the name of an active uniform block of a program
+    pub fn get_active_uniform_block_name(&self, program: &WebGLProgram, index: u32) -> String {
+        let len =
+            self.get_active_uniform_block_iv(program, index, UniformBlockParam::NameLength)[0];
+        let mut buf = vec![0u8; len.max(1) as usize];
+        let mut written = 0;
+        unsafe {
+            gl::GetActiveUniformBlockName(
+                program.0,
+                index,
+                buf.len() as _,
+                &mut written,
+                buf.as_mut_ptr() as *mut gl::types::GLchar,
+            );
+        }
+        check_gl_error("get_active_uniform_block_name");
+        buf.truncate(written as usize);
+        String::from_utf8_lossy(&buf).into_owned()
+    }
+
+    /// return a parameter of an active uniform block of a program.
+    /// [`UniformBlockParam::ActiveUniformIndices`] returns one value per active uniform of the block
+    pub fn get_active_uniform_block_iv(
+        &self,
+        program: &WebGLProgram,
+        index: u32,
+        pname: UniformBlockParam,
+    ) -> Vec<i32> {
+        let count = match pname {
+            UniformBlockParam::ActiveUniformIndices => {
+                self.get_active_uniform_block_iv(program, index, UniformBlockParam::ActiveUniforms)
+                    [0] as usize
+            }
+            _ => 1,
+        };
+        let mut res = vec![0; count];
+        if count > 0 {
+            unsafe {
+                gl::GetActiveUniformBlockiv(program.0, index, pname as _, res.as_mut_ptr());
+            }
+        }
+        check_gl_error("get_active_uniform_block_iv");
+        res
+    }
+
+    /// return the index of each named uniform of a program, None for inactive uniforms
+    pub fn get_uniform_indices(&self, program: &WebGLProgram, names: &[&str]) -> Vec<Option<u32>> {
+        let names: Vec<CString> = names
+            .iter()
+            .map(|name| self.c_string(name, "get_uniform_indices"))
+            .collect();
+        let pointers: Vec<*const gl::types::GLchar> =
+            names.iter().map(|name| name.as_ptr()).collect();
+        let mut indices = vec![gl::INVALID_INDEX; names.len()];
+        unsafe {
+            gl::GetUniformIndices(
+                program.0,
+                names.len() as _,
+                pointers.as_ptr(),
+                indices.as_mut_ptr(),
+            );
+        }
+        check_gl_error("get_uniform_indices");
+        indices
+            .into_iter()
+            .map(|index| {
+                if index == gl::INVALID_INDEX {
+                    None
+                } else {
+                    Some(index)
+                }
+            })
+            .collect()
+    }
+
     // pub fn get_active_uniform(&self, program: &WebGLProgram, location: u32) -> WebGLActiveInfo {
     //     let mut name: Vec<u8> = Vec::with_capacity(NAME_SIZE);
     //     let mut size = 0i32;
@@ -639,7 +1746,7 @@ impl GLContext {
     /// destroy a texture object
     pub fn delete_texture(&self, texture: &WebGLTexture) {
         unsafe {
-            gl::DeleteTextures(1, texture.0 as _);
+            gl::DeleteTextures(1, &texture.0);
         }
 
         check_gl_error("delete_texture");
@@ -665,6 +1772,12 @@ impl GLContext {
 
     /// select active texture unit
     pub fn active_texture(&self, active: u32) {
+        debug_assert!(
+            active < self.max_combined_texture_units(),
+            "active_texture : texture unit {} is out of range, the implementation supports {} units",
+            active,
+            self.max_combined_texture_units()
+        );
         unsafe {
             gl::ActiveTexture(gl::TEXTURE0 + active);
         }
@@ -672,6 +1785,16 @@ impl GLContext {
         check_gl_error("active_texture");
     }
 
+    /// return the number of texture units usable by [`GLContext::active_texture`]
+    pub fn max_combined_texture_units(&self) -> u32 {
+        get_integer(Parameter::MaxCombinedTextureImageUnits as _) as u32
+    }
+
+    /// return the active texture unit, as passed to [`GLContext::active_texture`]
+    pub fn get_active_texture(&self) -> u32 {
+        get_integer(Parameter::ActiveTexture as _) as u32 - gl::TEXTURE0
+    }
+
     /// bind a named 2D texture to a texturing target
     pub fn bind_texture(&self, texture: &WebGLTexture) {
         unsafe {
@@ -726,6 +1849,40 @@ impl GLContext {
         check_gl_error("blend_func");
     }
 
+    /// set the blend equation of one draw buffer (OpenGL 4.0)
+    pub fn blend_equation_indexed(&self, buf: u32, eq: BlendEquation) {
+        unsafe {
+            gl::BlendEquationi(buf, eq as _);
+        }
+        check_gl_error("blend_equation_indexed");
+    }
+
+    /// set the blend function of one draw buffer (OpenGL 4.0)
+    pub fn blend_func_indexed(&self, buf: u32, src: BlendMode, dst: BlendMode) {
+        unsafe {
+            gl::BlendFunci(buf, src as _, dst as _);
+        }
+        check_gl_error("blend_func_indexed");
+    }
+
+    /// enable a capability for one draw buffer only, for example [`Flag::Blend`]
+    pub fn enable_indexed(&self, flag: Flag, buf: u32) {
+        self.forget_flag_cache(flag as i32);
+        unsafe {
+            gl::Enablei(flag as _, buf);
+        }
+        check_gl_error("enable_indexed");
+    }
+
+    /// disable a capability for one draw buffer only
+    pub fn disable_indexed(&self, flag: Flag, buf: u32) {
+        self.forget_flag_cache(flag as i32);
+        unsafe {
+            gl::Disablei(flag as _, buf);
+        }
+        check_gl_error("disable_indexed");
+    }
+
     /// set the blend color
     pub fn blend_color(&self, r: f32, g: f32, b: f32, a: f32) {
         unsafe {
@@ -737,6 +1894,7 @@ impl GLContext {
 
     /// specify the value of a mat4 uniform variable for the current program object
     pub fn uniform_matrix_4fv(&self, location: &WebGLUniformLocation, value: &[[f32; 4]; 4]) {
+        self.debug_check_program_in_use("uniform_matrix_4fv");
         unsafe {
             gl::UniformMatrix4fv(*location.deref() as i32, 1, false as _, &value[0] as _);
         }
@@ -745,6 +1903,7 @@ impl GLContext {
 
     /// specify the value of a mat3 uniform variable for the current program object
     pub fn uniform_matrix_3fv(&self, location: &WebGLUniformLocation, value: &[[f32; 3]; 3]) {
+        self.debug_check_program_in_use("uniform_matrix_3fv");
         unsafe {
             gl::UniformMatrix3fv(*location.deref() as i32, 1, false as _, &value[0] as _);
         }
@@ -753,14 +1912,100 @@ impl GLContext {
 
     /// specify the value of a mat2 uniform variable for the current program object
     pub fn uniform_matrix_2fv(&self, location: &WebGLUniformLocation, value: &[[f32; 2]; 2]) {
+        self.debug_check_program_in_use("uniform_matrix_2fv");
         unsafe {
             gl::UniformMatrix2fv(*location.deref() as i32, 1, false as _, &value[0] as _);
         }
         check_gl_error("uniform_matrix_2fv");
     }
 
+    /// specify the value of a mat2x3 uniform variable (or array of mat2x3) for the current program object
+    pub fn uniform_matrix_2x3fv(&self, location: &WebGLUniformLocation, value: &[f32]) {
+        self.debug_check_program_in_use("uniform_matrix_2x3fv");
+        unsafe {
+            gl::UniformMatrix2x3fv(
+                *location.deref() as i32,
+                (value.len() / 6) as _,
+                false as _,
+                value.as_ptr(),
+            );
+        }
+        check_gl_error("uniform_matrix_2x3fv");
+    }
+
+    /// specify the value of a mat3x2 uniform variable (or array of mat3x2) for the current program object
+    pub fn uniform_matrix_3x2fv(&self, location: &WebGLUniformLocation, value: &[f32]) {
+        self.debug_check_program_in_use("uniform_matrix_3x2fv");
+        unsafe {
+            gl::UniformMatrix3x2fv(
+                *location.deref() as i32,
+                (value.len() / 6) as _,
+                false as _,
+                value.as_ptr(),
+            );
+        }
+        check_gl_error("uniform_matrix_3x2fv");
+    }
+
+    /// specify the value of a mat2x4 uniform variable (or array of mat2x4) for the current program object
+    pub fn uniform_matrix_2x4fv(&self, location: &WebGLUniformLocation, value: &[f32]) {
+        self.debug_check_program_in_use("uniform_matrix_2x4fv");
+        unsafe {
+            gl::UniformMatrix2x4fv(
+                *location.deref() as i32,
+                (value.len() / 8) as _,
+                false as _,
+                value.as_ptr(),
+            );
+        }
+        check_gl_error("uniform_matrix_2x4fv");
+    }
+
+    /// specify the value of a mat4x2 uniform variable (or array of mat4x2) for the current program object
+    pub fn uniform_matrix_4x2fv(&self, location: &WebGLUniformLocation, value: &[f32]) {
+        self.debug_check_program_in_use("uniform_matrix_4x2fv");
+        unsafe {
+            gl::UniformMatrix4x2fv(
+                *location.deref() as i32,
+                (value.len() / 8) as _,
+                false as _,
+                value.as_ptr(),
+            );
+        }
+        check_gl_error("uniform_matrix_4x2fv");
+    }
+
+    /// specify the value of a mat3x4 uniform variable (or array of mat3x4) for the current program object
+    pub fn uniform_matrix_3x4fv(&self, location: &WebGLUniformLocation, value: &[f32]) {
+        self.debug_check_program_in_use("uniform_matrix_3x4fv");
+        unsafe {
+            gl::UniformMatrix3x4fv(
+                *location.deref() as i32,
+                (value.len() / 12) as _,
+                false as _,
+                value.as_ptr(),
+            );
+        }
+        check_gl_error("uniform_matrix_3x4fv");
+    }
+
+    /// specify the value of a mat4x3 uniform variable (or array of mat4x3) for the current program object
+    pub fn uniform_matrix_4x3fv(&self, location: &WebGLUniformLocation, value: &[f32]) {
+        self.debug_check_program_in_use("uniform_matrix_4x3fv");
+        unsafe {
+            gl::UniformMatrix4x3fv(
+                *location.deref() as i32,
+                (value.len() / 12) as _,
+                false as _,
+                value.as_ptr(),
+            );
+        }
+        check_gl_error("uniform_matrix_4x3fv");
+    }
+
     /// specify the value of an int uniform variable for the current program object
     pub fn uniform_1i(&self, location: &WebGLUniformLocation, value: i32) {
+        self.debug_check_program_in_use("uniform_1i");
         unsafe {
             gl::Uniform1i(*location.deref() as i32, value as _);
         }
@@ -769,6 +2014,7 @@ impl GLContext {
 
     /// specify the value of a float uniform variable for the current program object
     pub fn uniform_1f(&self, location: &WebGLUniformLocation, value: f32) {
+        self.debug_check_program_in_use("uniform_1f");
         unsafe {
             gl::Uniform1f(*location.deref() as i32, value as _);
         }
@@ -777,6 +2023,7 @@ impl GLContext {
 
     /// specify the value of a vec2 uniform variable for the current program object
     pub fn uniform_2f(&self, location: &WebGLUniformLocation, value: (f32, f32)) {
+        self.debug_check_program_in_use("uniform_2f");
         unsafe {
             gl::Uniform2f(*location.deref() as _, value.0, value.1);
         }
@@ -785,6 +2032,7 @@ impl GLContext {
 
     /// specify the value of a vec3 uniform variable for the current program object
     pub fn uniform_3f(&self, location: &WebGLUniformLocation, value: (f32, f32, f32)) {
+        self.debug_check_program_in_use("uniform_3f");
         unsafe {
             gl::Uniform3f(*location.deref() as _, value.0, value.1, value.2);
         }
@@ -793,12 +2041,97 @@ impl GLContext {
 
     /// specify the value of a vec4 uniform variable for the current program object
     pub fn uniform_4f(&self, location: &WebGLUniformLocation, value: (f32, f32, f32, f32)) {
+        self.debug_check_program_in_use("uniform_4f");
         unsafe {
             gl::Uniform4f(*location.deref() as _, value.0, value.1, value.2, value.3);
         }
         check_gl_error("uniform_4f");
     }
 
+    /// specify the value of a uint uniform variable for the current program object
+    pub fn uniform_1ui(&self, location: &WebGLUniformLocation, value: u32) {
+        self.debug_check_program_in_use("uniform_1ui");
+        unsafe {
+            gl::Uniform1ui(*location.deref() as _, value);
+        }
+        check_gl_error("uniform_1ui");
+    }
+
+    /// specify the value of a uvec2 uniform variable for the current program object
+    pub fn uniform_2ui(&self, location: &WebGLUniformLocation, value: (u32, u32)) {
+        self.debug_check_program_in_use("uniform_2ui");
+        unsafe {
+            gl::Uniform2ui(*location.deref() as _, value.0, value.1);
+        }
+        check_gl_error("uniform_2ui");
+    }
+
+    /// specify the value of a uvec3 uniform variable for the current program object
+    pub fn uniform_3ui(&self, location: &WebGLUniformLocation, value: (u32, u32, u32)) {
+        self.debug_check_program_in_use("uniform_3ui");
+        unsafe {
+            gl::Uniform3ui(*location.deref() as _, value.0, value.1, value.2);
+        }
+        check_gl_error("uniform_3ui");
+    }
+
+    /// specify the value of a uvec4 uniform variable for the current program object
+    pub fn uniform_4ui(&self, location: &WebGLUniformLocation, value: (u32, u32, u32, u32)) {
+        self.debug_check_program_in_use("uniform_4ui");
+        unsafe {
+            gl::Uniform4ui(*location.deref() as _, value.0, value.1, value.2, value.3);
+        }
+        check_gl_error("uniform_4ui");
+    }
+
+    /// specify the values of an array of uint uniform variables for the current program object
+    pub fn uniform_1uiv(&self, location: &WebGLUniformLocation, value: &[u32]) {
+        self.debug_check_program_in_use("uniform_1uiv");
+        unsafe {
+            gl::Uniform1uiv(*location.deref() as _, value.len() as _, value.as_ptr());
+        }
+        check_gl_error("uniform_1uiv");
+    }
+
+    /// specify the values of an array of uvec2 uniform variables for the current program object
+    pub fn uniform_2uiv(&self, location: &WebGLUniformLocation, value: &[u32]) {
+        self.debug_check_program_in_use("uniform_2uiv");
+        unsafe {
+            gl::Uniform2uiv(
+                *location.deref() as _,
+                (value.len() / 2) as _,
+                value.as_ptr(),
+            );
+        }
+        check_gl_error("uniform_2uiv");
+    }
+
+    /// specify the values of an array of uvec3 uniform variables for the current program object
+    pub fn uniform_3uiv(&self, location: &WebGLUniformLocation, value: &[u32]) {
+        self.debug_check_program_in_use("uniform_3uiv");
+        unsafe {
+            gl::Uniform3uiv(
+                *location.deref() as _,
+                (value.len() / 3) as _,
+                value.as_ptr(),
+            );
+        }
+        check_gl_error("uniform_3uiv");
+    }
+
+    /// specify the values of an array of uvec4 uniform variables for the current program object
+    pub fn uniform_4uiv(&self, location: &WebGLUniformLocation, value: &[u32]) {
+        self.debug_check_program_in_use("uniform_4uiv");
+        unsafe {
+            gl::Uniform4uiv(
+                *location.deref() as _,
+                (value.len() / 4) as _,
+                value.as_ptr(),
+            );
+        }
+        check_gl_error("uniform_4uiv");
+    }
+
     /// set texture integer parameters
     pub fn tex_parameteri(&self, kind: TextureKind, pname: TextureParameter, param: i32) {
         unsafe {
@@ -841,20 +2174,20 @@ impl GLContext {
         check_gl_error("bind_vertex_array");
     }
 
-    /// current vertex array object is not bound to the current state anymore
+    /// current vertex array object is not bound to the current state anymore,
+    /// the [default one](GLContext::default_vertex_array) is bound back
     pub fn unbind_vertex_array(&self, _vao: &WebGLVertexArray) {
         unsafe {
-            gl::BindVertexArray(0);
+            gl::BindVertexArray(self.default_vertex_array.as_ref().map_or(0, |vao| vao.0));
         }
         check_gl_error("unbind_vertex_array");
     }
 
     /// specify which color buffers are to be drawn into
     pub fn draw_buffer(&self, buffers: &[ColorBuffer]) {
+        let buffers: Vec<u32> = buffers.iter().map(|value| *value as u32).collect();
         unsafe {
-            for value in buffers {
-                gl::DrawBuffer(*value as _);
-            }
+            gl::DrawBuffers(buffers.len() as _, buffers.as_ptr());
         }
         check_gl_error("draw_buffer");
     }
@@ -908,6 +2241,38 @@ impl GLContext {
         check_gl_error("framebuffer_texture2d");
     }
 
+    /// attach a texture to a framebuffer, rendering into it with `samples` samples per pixel
+    /// and resolving implicitly. Requires GL_EXT_multisampled_render_to_texture, which desktop drivers rarely expose.
+    /// Otherwise, render into a multisample renderbuffer and blit it into the texture
+    pub fn framebuffer_texture2d_multisample(
+        &self,
+        target: Buffers,
+        attachment: Buffers,
+        textarget: TextureBindPoint,
+        texture: &WebGLTexture,
+        level: i32,
+        samples: i32,
+    ) {
+        let ptr = FRAMEBUFFER_TEXTURE2D_MULTISAMPLE.load(Ordering::Relaxed);
+        if ptr == 0 {
+            self.log_at(
+                LogLevel::Error,
+                "ERROR framebuffer_texture2d_multisample requires GL_EXT_multisampled_render_to_texture",
+            );
+            return;
+        }
+        let attach: FramebufferTexture2DMultisampleFn = unsafe { std::mem::transmute(ptr) };
+        attach(
+            target as u32,
+            attachment as u32,
+            textarget as u32,
+            texture.0,
+            level,
+            samples,
+        );
+        check_gl_error("framebuffer_texture2d_multisample");
+    }
+
     /// unbind a framebuffer
     pub fn unbind_framebuffer(&self, buffer: Buffers) {
         unsafe {
@@ -916,4 +2281,71 @@ impl GLContext {
 
         check_gl_error("unbind_framebuffer");
     }
+
+    /// return the completeness status of the framebuffer bound to target
+    pub fn check_framebuffer_status(&self, target: Buffers) -> FramebufferStatus {
+        let status = unsafe { gl::CheckFramebufferStatus(target as u32) };
+        check_gl_error("check_framebuffer_status");
+        FramebufferStatus::from(status)
+    }
+
+    /// create a new renderbuffer
+    pub fn create_renderbuffer(&self) -> WebGLRenderBuffer {
+        let mut rb = WebGLRenderBuffer(0);
+        unsafe {
+            gl::GenRenderbuffers(1, &mut rb.0);
+        }
+        check_gl_error("create_renderbuffer");
+        rb
+    }
+
+    /// destroy a renderbuffer
+    pub fn delete_renderbuffer(&self, rb: &WebGLRenderBuffer) {
+        unsafe {
+            gl::DeleteRenderbuffers(1, &rb.0);
+        }
+        check_gl_error("delete_renderbuffer");
+    }
+
+    /// bind a renderbuffer to the current state
+    pub fn bind_renderbuffer(&self, rb: &WebGLRenderBuffer) {
+        unsafe {
+            gl::BindRenderbuffer(gl::RENDERBUFFER, rb.0);
+        }
+        check_gl_error("bind_renderbuffer");
+    }
+
+    /// unbind a renderbuffer
+    pub fn unbind_renderbuffer(&self) {
+        unsafe {
+            gl::BindRenderbuffer(gl::RENDERBUFFER, 0);
+        }
+        check_gl_error("unbind_renderbuffer");
+    }
+
+    /// allocate the storage of the bound renderbuffer
+    pub fn renderbuffer_storage(&self, internal_format: PixelFormat, width: u16, height: u16) {
+        unsafe {
+            gl::RenderbufferStorage(
+                gl::RENDERBUFFER,
+                internal_format as _,
+                width as _,
+                height as _,
+            );
+        }
+        check_gl_error("renderbuffer_storage");
+    }
+
+    /// attach a renderbuffer to a framebuffer
+    pub fn framebuffer_renderbuffer(
+        &self,
+        target: Buffers,
+        attachment: Buffers,
+        rb: &WebGLRenderBuffer,
+    ) {
+        unsafe {
+            gl::FramebufferRenderbuffer(target as u32, attachment as u32, gl::RENDERBUFFER, rb.0);
+        }
+        check_gl_error("framebuffer_renderbuffer");
+    }
 }